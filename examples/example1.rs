@@ -4,7 +4,6 @@
 //  Distributed under the Boost Software License, Version 1.0. (See accompanying
 //  file LICENSE_1_0.txt or copy at http://www.boost.org/LICENSE_1_0.txt)
 //
-use std::fs::read_to_string;
 use std::path::Path;
 use std::vec;
 
@@ -16,7 +15,7 @@ fn main() {
 
     let mut df : DataFrame = DataFrame::new();
     
-    df.load_csv(&path);
+    df.load_csv(path).expect("the sample csv loads");
     df.println();
 
     let groupby = df.group_by(vec!["ELEVATION", "STATION"]);
@@ -28,7 +27,7 @@ fn main() {
         for (dt, v) in vs {
             println!("{} {}", dt, v);
         }
-        println!("");
+        println!();
     }
 
     groupby.print();
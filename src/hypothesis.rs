@@ -0,0 +1,650 @@
+//  Copyright (c) 2020 Christopher Taylor
+//
+//  SPDX-License-Identifier: BSL-1.0
+//  Distributed under the Boost Software License, Version 1.0. (See accompanying
+//  file LICENSE_1_0.txt or copy at http://www.boost.org/LICENSE_1_0.txt)
+//
+// statistical hypothesis tests over series, crosstabs and groups
+//
+// nulls (and NaN) are dropped from every sample before testing,
+// p-values come from the t, F, chi-square, normal and Kolmogorov
+// distributions evaluated through the incomplete beta and gamma
+// functions (Numerical Recipes, 2nd ed., chapter 6)
+//
+use crate::{compensated_sum, average_ranks, DataFrame, DataFrameError, DataTypes, Group};
+
+// outcome of a test; `df` holds the degrees of freedom, none for the
+// rank and Kolmogorov-Smirnov tests, (between, within) for anova
+//
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestResult {
+    pub statistic : f64,
+    pub pvalue : f64,
+    pub df : Vec<f64>,
+}
+
+// the alternative hypothesis; `Less` means the first sample's
+// location is below the second's (or below `mu`)
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Alternative {
+    TwoSided,
+    Less,
+    Greater,
+}
+
+const EPS : f64 = 1.0e-15;
+const FPMIN : f64 = 1.0e-300;
+const MAX_ITERATIONS : usize = 500;
+
+// log gamma by the Lanczos approximation (g = 7, n = 9)
+//
+fn ln_gamma(x : f64) -> f64 {
+    const COEFFICIENTS : [f64; 9] = [
+        0.999_999_999_999_809_9, 676.520_368_121_885_1, -1_259.139_216_722_402_8,
+        771.323_428_777_653_1, -176.615_029_162_140_6, 12.507_343_278_686_905,
+        -0.138_571_095_265_720_12, 9.984_369_578_019_572e-6, 1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let a = COEFFICIENTS.iter().enumerate().skip(1).fold(COEFFICIENTS[0], |a, (i, c)| a + c / (x + i as f64));
+    let t = x + 7.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+}
+
+fn guard(x : f64) -> f64 {
+    if x.abs() < FPMIN { FPMIN } else { x }
+}
+
+// continued fraction of the incomplete beta function, modified Lentz
+//
+fn beta_continued_fraction(a : f64, b : f64, x : f64) -> f64 {
+    let (qab, qap, qam) = (a + b, a + 1.0, a - 1.0);
+    let mut c = 1.0;
+    let mut d = 1.0 / guard(1.0 - qab * x / qap);
+    let mut h = d;
+
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 / guard(1.0 + aa * d);
+        c = guard(1.0 + aa / c);
+        h *= d * c;
+
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 / guard(1.0 + aa * d);
+        c = guard(1.0 + aa / c);
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < EPS {
+            break;
+        }
+    }
+
+    h
+}
+
+// regularized incomplete beta function I_x(a, b)
+//
+fn beta_inc(a : f64, b : f64, x : f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+// regularized upper incomplete gamma function Q(a, x), by its series
+// below a + 1 and its continued fraction above
+//
+fn gamma_q(a : f64, x : f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    if x <= 0.0 {
+        return 1.0;
+    }
+
+    let front = (-x + a * x.ln() - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        let mut ap = a;
+        let mut delta = 1.0 / a;
+        let mut sum = delta;
+        for _ in 0..MAX_ITERATIONS {
+            ap += 1.0;
+            delta *= x / ap;
+            sum += delta;
+            if delta.abs() < sum.abs() * EPS {
+                break;
+            }
+        }
+        return 1.0 - sum * front;
+    }
+
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / FPMIN;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..=MAX_ITERATIONS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = 1.0 / guard(an * d + b);
+        c = guard(b + an / c);
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPS {
+            break;
+        }
+    }
+    front * h
+}
+
+// P(T > t) for Student's t with `df` degrees of freedom
+//
+fn student_t_sf(t : f64, df : f64) -> f64 {
+    let tail = 0.5 * beta_inc(df / 2.0, 0.5, df / (df + t * t));
+    if t > 0.0 { tail } else { 1.0 - tail }
+}
+
+// P(Z > z) for the standard normal
+//
+pub(crate) fn normal_sf(z : f64) -> f64 {
+    let tail = 0.5 * gamma_q(0.5, z * z / 2.0);
+    if z > 0.0 { tail } else { 1.0 - tail }
+}
+
+// standard normal quantile, Acklam's rational approximation refined
+// by one Halley step; infinite at 0 and 1
+//
+pub(crate) fn normal_ppf(p : f64) -> f64 {
+    const A : [f64; 6] = [-3.969_683_028_665_376e1, 2.209_460_984_245_205e2, -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2, -3.066_479_806_614_716e1, 2.506_628_277_459_239];
+    const B : [f64; 5] = [-5.447_609_879_822_406e1, 1.615_858_368_580_409e2, -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1, -1.328_068_155_288_572e1];
+    const C : [f64; 6] = [-7.784_894_002_430_293e-3, -3.223_964_580_411_365e-1, -2.400_758_277_161_838,
+        -2.549_732_539_343_734, 4.374_664_141_464_968, 2.938_163_982_698_783];
+    const D : [f64; 4] = [7.784_695_709_041_462e-3, 3.224_671_290_700_398e-1, 2.445_134_137_142_996, 3.754_408_661_907_416];
+
+    if p.is_nan() || !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p == 1.0 {
+        return f64::INFINITY;
+    }
+
+    let tail = |q : f64| (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) /
+        ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0);
+
+    let x = if p < 0.024_25 {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - 0.024_25 {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q /
+            (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    };
+
+    let cdf = if x < 0.0 { normal_sf(-x) } else { 1.0 - normal_sf(x) };
+    let e = cdf - p;
+    let u = e * (2.0 * std::f64::consts::PI).sqrt() * (x * x / 2.0).exp();
+    x - u / (1.0 + x * u / 2.0)
+}
+
+// P(F > f) for the F distribution with (d1, d2) degrees of freedom
+//
+fn f_sf(f : f64, d1 : f64, d2 : f64) -> f64 {
+    if f <= 0.0 { 1.0 } else { beta_inc(d2 / 2.0, d1 / 2.0, d2 / (d2 + d1 * f)) }
+}
+
+// P(X > x) for chi-square with `df` degrees of freedom
+//
+fn chi_square_sf(x : f64, df : f64) -> f64 {
+    gamma_q(df / 2.0, x / 2.0)
+}
+
+// Kolmogorov distribution Q_KS(lambda) = 2 sum (-1)^(j-1) exp(-2 j^2 lambda^2)
+//
+fn kolmogorov_sf(lambda : f64) -> f64 {
+    let a2 = -2.0 * lambda * lambda;
+    let mut sign = 2.0;
+    let mut sum = 0.0;
+    let mut previous = 0.0;
+
+    for j in 1..=100 {
+        let term = sign * (a2 * (j * j) as f64).exp();
+        sum += term;
+        if term.abs() <= 0.001 * previous || term.abs() <= 1.0e-8 * sum {
+            return sum.clamp(0.0, 1.0);
+        }
+        sign = -sign;
+        previous = term.abs();
+    }
+
+    // the series fails to converge only for tiny lambda
+    1.0
+}
+
+// p-value of a statistic from its upper tail function
+//
+fn p_value(statistic : f64, sf : &dyn Fn(f64) -> f64, alternative : Alternative) -> f64 {
+    match alternative {
+        Alternative::Greater => sf(statistic),
+        Alternative::Less => 1.0 - sf(statistic),
+        Alternative::TwoSided => (2.0 * sf(statistic.abs())).min(1.0),
+    }
+}
+
+// non-null values of a numeric sample
+//
+fn sample(series : &[DataTypes], name : &str) -> Result<Vec<f64>, DataFrameError> {
+    if series.iter().any(|v| matches!(v, DataTypes::StringType(_))) {
+        return Err(DataFrameError::InvalidArgument(format!("{} sample is not numeric", name)));
+    }
+
+    Ok(series.iter().filter(|v| !v.is_null()).map(|v| v.fvalue()).collect())
+}
+
+fn at_least(values : &[f64], n : usize, name : &str) -> Result<(), DataFrameError> {
+    if values.len() < n {
+        return Err(DataFrameError::InvalidArgument(format!("{} sample needs at least {} values, has {}", name, n, values.len())));
+    }
+    Ok(())
+}
+
+// mean and sample variance (n-1)
+//
+fn mean_variance(values : &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = compensated_sum(values.iter().cloned()) / n;
+    let variance = compensated_sum(values.iter().map(|x| (x - mean) * (x - mean))) / (n - 1.0);
+    (mean, variance)
+}
+
+// one sample t-test of the mean against `mu`
+//
+pub fn ttest_1samp(x : &[DataTypes], mu : f64, alternative : Alternative) -> Result<TestResult, DataFrameError> {
+    let x = sample(x, "x")?;
+    at_least(&x, 2, "x")?;
+
+    let (mean, variance) = mean_variance(&x);
+    let df = (x.len() - 1) as f64;
+    let t = (mean - mu) / (variance / x.len() as f64).sqrt();
+
+    Ok(TestResult{ statistic : t, pvalue : p_value(t, &|s| student_t_sf(s, df), alternative), df : vec![df] })
+}
+
+// two sample t-test of equal means; Student's pooled variance test
+// with `equal_var`, Welch's test with Satterthwaite's df otherwise
+//
+pub fn ttest_ind(x : &[DataTypes], y : &[DataTypes], equal_var : bool, alternative : Alternative) -> Result<TestResult, DataFrameError> {
+    let x = sample(x, "x")?;
+    let y = sample(y, "y")?;
+    at_least(&x, 2, "x")?;
+    at_least(&y, 2, "y")?;
+
+    let (n1, n2) = (x.len() as f64, y.len() as f64);
+    let (m1, v1) = mean_variance(&x);
+    let (m2, v2) = mean_variance(&y);
+
+    let (se, df) = if equal_var {
+        let pooled = ((n1 - 1.0) * v1 + (n2 - 1.0) * v2) / (n1 + n2 - 2.0);
+        ((pooled * (1.0 / n1 + 1.0 / n2)).sqrt(), n1 + n2 - 2.0)
+    } else {
+        let (a, b) = (v1 / n1, v2 / n2);
+        ((a + b).sqrt(), (a + b) * (a + b) / (a * a / (n1 - 1.0) + b * b / (n2 - 1.0)))
+    };
+
+    let t = (m1 - m2) / se;
+    Ok(TestResult{ statistic : t, pvalue : p_value(t, &|s| student_t_sf(s, df), alternative), df : vec![df] })
+}
+
+// paired t-test on the differences x - y of the rows where both are
+// non-null
+//
+pub fn ttest_rel(x : &[DataTypes], y : &[DataTypes], alternative : Alternative) -> Result<TestResult, DataFrameError> {
+    if x.len() != y.len() {
+        return Err(DataFrameError::InvalidArgument(format!("paired samples differ in length, {} and {}", x.len(), y.len())));
+    }
+    sample(x, "x")?;
+    sample(y, "y")?;
+
+    let differences : Vec<DataTypes> = x.iter().zip(y.iter()).filter(|(a, b)| !a.is_null() && !b.is_null())
+        .map(|(a, b)| DataTypes::FloatType(crate::FloatType{value : a.fvalue() - b.fvalue()})).collect();
+
+    ttest_1samp(&differences, 0.0, alternative)
+}
+
+// Mann-Whitney U test by the normal approximation, with tie and
+// continuity corrections; the statistic is U of `x`
+//
+pub fn mann_whitney_u(x : &[DataTypes], y : &[DataTypes], alternative : Alternative) -> Result<TestResult, DataFrameError> {
+    let x = sample(x, "x")?;
+    let y = sample(y, "y")?;
+    at_least(&x, 1, "x")?;
+    at_least(&y, 1, "y")?;
+
+    let (n1, n2) = (x.len() as f64, y.len() as f64);
+    let n = n1 + n2;
+    let combined : Vec<f64> = x.iter().chain(y.iter()).cloned().collect();
+    let ranks = average_ranks(&combined);
+
+    let u1 = ranks[..x.len()].iter().sum::<f64>() - n1 * (n1 + 1.0) / 2.0;
+    let u2 = n1 * n2 - u1;
+
+    let mut sorted = combined;
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut ties = 0.0;
+    let mut start = 0;
+    while start < sorted.len() {
+        let end = start + sorted[start..].iter().take_while(|&&v| v == sorted[start]).count();
+        let t = (end - start) as f64;
+        ties += t * t * t - t;
+        start = end;
+    }
+
+    let mu = n1 * n2 / 2.0;
+    let sigma = (n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)))).sqrt();
+    let z = |u : f64| (u - mu - 0.5) / sigma;
+
+    let pvalue = match alternative {
+        Alternative::Greater => normal_sf(z(u1)),
+        Alternative::Less => normal_sf(z(u2)),
+        Alternative::TwoSided => (2.0 * normal_sf(z(u1.max(u2)))).min(1.0),
+    };
+
+    Ok(TestResult{ statistic : u1, pvalue, df : Vec::new() })
+}
+
+// two sample Kolmogorov-Smirnov test; the statistic is the largest
+// distance between the empirical distribution functions, the p-value
+// its asymptotic two-sided probability with Stephens' correction
+//
+pub fn ks_2samp(x : &[DataTypes], y : &[DataTypes]) -> Result<TestResult, DataFrameError> {
+    let mut x = sample(x, "x")?;
+    let mut y = sample(y, "y")?;
+    at_least(&x, 1, "x")?;
+    at_least(&y, 1, "y")?;
+
+    x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    y.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let (n1, n2) = (x.len() as f64, y.len() as f64);
+    let (mut i, mut j) = (0, 0);
+    let mut d : f64 = 0.0;
+    while i < x.len() && j < y.len() {
+        let v = x[i].min(y[j]);
+        while i < x.len() && x[i] <= v {
+            i += 1;
+        }
+        while j < y.len() && y[j] <= v {
+            j += 1;
+        }
+        d = d.max((i as f64 / n1 - j as f64 / n2).abs());
+    }
+
+    let en = (n1 * n2 / (n1 + n2)).sqrt();
+    Ok(TestResult{ statistic : d, pvalue : kolmogorov_sf((en + 0.12 + 0.11 / en) * d), df : Vec::new() })
+}
+
+// chi-square test of independence on a crosstab built with
+// `Normalize::Off` and no margins; `index` names the crosstab's row
+// key columns, every other column holds counts. no continuity
+// correction is applied
+//
+pub fn chi_square_independence(table : &DataFrame, index : Vec<&str>) -> Result<TestResult, DataFrameError> {
+    for name in index.iter() {
+        table.find_column_index(name)?;
+    }
+
+    let counts : Vec<Vec<f64>> = table.labels.iter().zip(table.columns.iter()).filter(|(l, _)| !index.contains(&l.as_str())).map(|(l, c)| {
+        if l == "All" {
+            return Err(DataFrameError::InvalidArgument(String::from("crosstab must be built without margins")));
+        }
+        c.iter().map(|v| match v {
+            DataTypes::IntegerType(_) | DataTypes::FloatType(_) if v.fvalue() >= 0.0 => Ok(v.fvalue()),
+            _ => Err(DataFrameError::InvalidArgument(format!("column '{}' does not hold counts", l))),
+        }).collect()
+    }).collect::<Result<_, _>>()?;
+
+    let ncols = counts.len();
+    let nrows = counts.first().map_or(0, |c| c.len());
+    if nrows < 2 || ncols < 2 {
+        return Err(DataFrameError::InvalidArgument(format!("crosstab of {}x{} counts needs at least 2x2", nrows, ncols)));
+    }
+
+    let row_totals : Vec<f64> = (0..nrows).map(|i| counts.iter().map(|c| c[i]).sum()).collect();
+    let col_totals : Vec<f64> = counts.iter().map(|c| c.iter().sum()).collect();
+    let total : f64 = col_totals.iter().sum();
+
+    if row_totals.iter().chain(col_totals.iter()).any(|&t| t == 0.0) {
+        return Err(DataFrameError::InvalidArgument(String::from("crosstab has a row or column without counts")));
+    }
+
+    let mut statistic = 0.0;
+    for (j, column) in counts.iter().enumerate() {
+        for (i, &observed) in column.iter().enumerate() {
+            let expected = row_totals[i] * col_totals[j] / total;
+            statistic += (observed - expected) * (observed - expected) / expected;
+        }
+    }
+
+    let df = ((nrows - 1) * (ncols - 1)) as f64;
+    Ok(TestResult{ statistic, pvalue : chi_square_sf(statistic, df), df : vec![df] })
+}
+
+// one-way anova of `column_name` across the groups of each grouping
+// column, one result per grouping column; empty groups are ignored
+//
+pub fn anova_oneway(group : &Group, column_name : &str) -> Result<Vec<TestResult>, DataFrameError> {
+    let idx = group.df.find_column_index(column_name)?;
+    let column = &group.df.columns[idx];
+    sample(column, column_name)?;
+
+    group.indices.iter().map(|groups| {
+        let samples : Vec<Vec<f64>> = groups.values().map(|rows|
+            rows.iter().map(|&r| &column[r]).filter(|v| !v.is_null()).map(|v| v.fvalue()).collect::<Vec<f64>>()
+        ).filter(|s| !s.is_empty()).collect();
+
+        let k = samples.len();
+        let n : usize = samples.iter().map(|s| s.len()).sum();
+        if k < 2 || n <= k {
+            return Err(DataFrameError::InvalidArgument(format!("anova needs at least two groups and more values than groups, has {} groups of {} values", k, n)));
+        }
+
+        let grand = compensated_sum(samples.iter().flat_map(|s| s.iter().cloned())) / n as f64;
+        let (mut between, mut within) = (0.0, 0.0);
+        for s in samples.iter() {
+            let mean = compensated_sum(s.iter().cloned()) / s.len() as f64;
+            between += s.len() as f64 * (mean - grand) * (mean - grand);
+            within += compensated_sum(s.iter().map(|x| (x - mean) * (x - mean)));
+        }
+
+        let (d1, d2) = ((k - 1) as f64, (n - k) as f64);
+        let f = (between / d1) / (within / d2);
+        Ok(TestResult{ statistic : f, pvalue : f_sf(f, d1, d2), df : vec![d1, d2] })
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{close, f, floats, frame, ints, strs};
+    use crate::{Normalize, Series};
+
+    // reference statistics and p-values below match scipy.stats and
+    // were checked against the exact distributions in mpmath
+
+    #[test]
+    fn distributions_match_reference_values() {
+        close(normal_sf(1.96), 0.024997895148220435, 1e-12);
+        close(normal_sf(-1.96), 0.9750021048517795, 1e-12);
+        close(normal_ppf(0.975), 1.9599639845400543, 1e-9);
+        close(normal_ppf(0.001), -3.0902323061678136, 1e-9);
+        close(normal_ppf(0.5), 0.0, 1e-12);
+    }
+
+    #[test]
+    fn one_sample_t_test() {
+        let x = floats(&[5.1, 4.9, 5.6, 5.8, 6.0, 5.5, 5.3]);
+
+        let result = ttest_1samp(&x, 5.0, Alternative::TwoSided).unwrap();
+        close(result.statistic, 3.127849019765158, 1e-12);
+        close(result.pvalue, 0.02038029857335171, 1e-9);
+        assert_eq!(result.df, vec![6.0]);
+
+        close(ttest_1samp(&x, 5.0, Alternative::Greater).unwrap().pvalue, 0.010190149286675856, 1e-9);
+        close(ttest_1samp(&x, 5.0, Alternative::Less).unwrap().pvalue, 0.9898098507133242, 1e-9);
+    }
+
+    #[test]
+    fn two_sample_t_tests_pooled_and_welch() {
+        let x = floats(&[19.7, 20.4, 21.1, 20.9, 19.5]);
+        let y = floats(&[18.2, 19.0, 18.9, 19.7, 18.4, 19.1]);
+
+        let student = ttest_ind(&x, &y, true, Alternative::TwoSided).unwrap();
+        close(student.statistic, 3.8396701052748545, 1e-12);
+        close(student.pvalue, 0.003968531315757298, 1e-9);
+        assert_eq!(student.df, vec![9.0]);
+
+        let welch = ttest_ind(&x, &y, false, Alternative::TwoSided).unwrap();
+        close(welch.statistic, 3.7342970022540327, 1e-12);
+        close(welch.df[0], 7.367748245775128, 1e-12);
+        close(welch.pvalue, 0.0066699398825849346, 1e-9);
+    }
+
+    #[test]
+    fn paired_t_test_and_mismatched_lengths() {
+        let x = ints(&[10, 12, 9, 11, 13]);
+        let y = ints(&[9, 11, 9, 10, 11]);
+
+        let result = ttest_rel(&x, &y, Alternative::TwoSided).unwrap();
+        close(result.statistic, 3.1622776601683795, 1e-12);
+        close(result.pvalue, 0.03410942316740963, 1e-9);
+        assert_eq!(result.df, vec![4.0]);
+
+        assert!(matches!(ttest_rel(&x, &y[..4], Alternative::TwoSided), Err(DataFrameError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn mann_whitney_with_ties() {
+        let x = floats(&[1.0, 4.0, 2.0, 7.0, 5.0, 5.0]);
+        let y = floats(&[3.0, 6.0, 8.0, 9.0, 8.0]);
+
+        let result = mann_whitney_u(&x, &y, Alternative::TwoSided).unwrap();
+        assert_eq!(result.statistic, 5.0);
+        close(result.pvalue, 0.08143973230450291, 1e-9);
+        assert!(result.df.is_empty());
+
+        close(mann_whitney_u(&x, &y, Alternative::Less).unwrap().pvalue, 0.040719866152251455, 1e-9);
+        close(mann_whitney_u(&x, &y, Alternative::Greater).unwrap().pvalue, 0.9729354857101334, 1e-9);
+    }
+
+    #[test]
+    fn kolmogorov_smirnov_two_samples() {
+        let x = floats(&[0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06, 0.64,
+            -0.82, 0.37, 1.77, 1.09, -1.28, 2.36, 1.31, 1.05, -0.32, -0.4, 1.06, -2.47]);
+        let y = floats(&[2.2, 1.66, 1.38, 0.2, 0.36, 0.0, 0.96, 1.56, 0.44, 1.5, -0.3, 0.66, 2.31, 3.29,
+            -0.27, -0.37, 0.38, 0.7, 0.52, -0.72]);
+
+        let result = ks_2samp(&x, &y).unwrap();
+        close(result.statistic, 0.23, 1e-12);
+        close(result.pvalue, 0.5411245537216748, 1e-9);
+
+        let same = ks_2samp(&x, &x).unwrap();
+        assert_eq!(same.statistic, 0.0);
+        assert_eq!(same.pvalue, 1.0);
+    }
+
+    #[test]
+    fn chi_square_on_crosstabs() {
+        let table = frame(vec![("g", strs(&["a", "b"])), ("x", ints(&[10, 30])), ("y", ints(&[20, 25]))]);
+        let result = chi_square_independence(&table, vec!["g"]).unwrap();
+        close(result.statistic, 3.505892255892256, 1e-12);
+        close(result.pvalue, 0.06115089757606769, 1e-9);
+        assert_eq!(result.df, vec![1.0]);
+
+        let table = frame(vec![("g", strs(&["a", "b", "c"])), ("x", ints(&[12, 8, 4])), ("y", ints(&[5, 15, 6])), ("z", ints(&[7, 9, 14]))]);
+        let result = chi_square_independence(&table, vec!["g"]).unwrap();
+        close(result.statistic, 12.316239316239317, 1e-12);
+        close(result.pvalue, 0.015148218734524379, 1e-9);
+        assert_eq!(result.df, vec![4.0]);
+    }
+
+    #[test]
+    fn chi_square_rejects_margins_small_and_empty_tables() {
+        let data = frame(vec![("sex", strs(&["m", "f", "m", "f"])), ("smoker", strs(&["y", "n", "n", "y"]))]);
+        let margins = data.crosstab(vec!["sex"], vec!["smoker"], true, Normalize::Off).unwrap();
+        assert!(matches!(chi_square_independence(&margins, vec!["sex"]), Err(DataFrameError::InvalidArgument(_))));
+
+        let single = frame(vec![("g", strs(&["a", "b"])), ("x", ints(&[1, 2]))]);
+        assert!(matches!(chi_square_independence(&single, vec!["g"]), Err(DataFrameError::InvalidArgument(_))));
+
+        let zeros = frame(vec![("g", strs(&["a", "b"])), ("x", ints(&[0, 0])), ("y", ints(&[3, 4]))]);
+        assert!(matches!(chi_square_independence(&zeros, vec!["g"]), Err(DataFrameError::InvalidArgument(_))));
+
+        let negative = frame(vec![("g", strs(&["a", "b"])), ("x", ints(&[-1, 2])), ("y", ints(&[3, 4]))]);
+        assert!(matches!(chi_square_independence(&negative, vec!["g"]), Err(DataFrameError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn one_way_anova() {
+        let df = frame(vec![
+            ("g", strs(&["a", "a", "a", "a", "a", "a", "b", "b", "b", "b", "b", "b", "c", "c", "c", "c", "c", "c"])),
+            ("v", ints(&[6, 8, 4, 5, 3, 4, 8, 12, 9, 11, 6, 8, 13, 9, 11, 8, 7, 12])),
+        ]);
+        let results = anova_oneway(&df.group_by(vec!["g"]), "v").unwrap();
+        assert_eq!(results.len(), 1);
+        close(results[0].statistic, 9.264705882352942, 1e-12);
+        close(results[0].pvalue, 0.0023987773293929083, 1e-9);
+        assert_eq!(results[0].df, vec![2.0, 15.0]);
+
+        let single = frame(vec![("g", strs(&["a", "a"])), ("v", ints(&[1, 2]))]);
+        assert!(matches!(anova_oneway(&single.group_by(vec!["g"]), "v"), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(anova_oneway(&df.group_by(vec!["g"]), "g"), Err(DataFrameError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn samples_drop_nulls_and_reject_strings_and_short_input() {
+        let mut x = floats(&[5.1, 4.9, 5.6, 5.8, 6.0, 5.5, 5.3]);
+        x.push(DataTypes::null());
+        x.push(f(f64::NAN));
+        close(ttest_1samp(&x, 5.0, Alternative::TwoSided).unwrap().statistic, 3.127849019765158, 1e-12);
+
+        let words = strs(&["a", "b", "c"]);
+        assert!(matches!(ttest_1samp(&words, 0.0, Alternative::TwoSided), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(ks_2samp(&x, &words), Err(DataFrameError::InvalidArgument(_))));
+
+        let empty : Series = Vec::new();
+        let nulls = vec![DataTypes::null(), DataTypes::null()];
+        assert!(matches!(ttest_1samp(&empty, 0.0, Alternative::TwoSided), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(ttest_1samp(&nulls, 0.0, Alternative::TwoSided), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(ttest_ind(&x, &floats(&[1.0]), true, Alternative::TwoSided), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(mann_whitney_u(&x, &empty, Alternative::TwoSided), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(ks_2samp(&nulls, &x), Err(DataFrameError::InvalidArgument(_))));
+    }
+}
@@ -8,14 +8,22 @@
 //
 // compile flag : rustc src\main.rs -C target-cpu=<pick one from target-cpus> -C target-feature=+sse3,+avx
 //
+
 use std::fs::read_to_string;
 use std::path::Path;
 use std::vec;
 use std::hash::{Hash, Hasher};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::error::Error;
+use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::DefaultHasher;
-use std::clone;
-use std::mem;
+
+pub mod hypothesis;
+
+#[cfg(test)]
+mod test_util;
 
 #[derive(Copy, Clone)]
 pub struct FloatType {
@@ -31,13 +39,19 @@ pub struct StringType {
     value : String,
 }
 
+// marks a missing value; numeric NaN is also treated
+// as missing by `DataTypes::is_null`
+//
+#[derive(Copy, Clone)]
+pub struct NullType {}
+
 impl FloatType {
 
     // snagged this from stackoverflow
     // TODO need to identify the link
     //
     pub fn integer_decode(val: f64) -> Vec<u64> {
-        let bits: u64 = unsafe { mem::transmute(val) };
+        let bits: u64 = val.to_bits();
         let sign: i8 = if bits >> 63 == 0 { 1 } else { -1 };
         let mut exponent: i16 = ((bits >> 52) & 0x7ff) as i16;
         let mantissa = if exponent == 0 {
@@ -47,16 +61,25 @@ impl FloatType {
         };
     
         exponent -= 1023 + 52;
-        vec![mantissa as u64, exponent as u64, sign as u64]
+        vec![mantissa, exponent as u64, sign as u64]
+    }
+}
+
+impl FloatType {
+
+    // -0.0/0.0 and every NaN payload compare equal, so
+    // they need to hash to the same value as well
+    //
+    fn canonical(val: f64) -> f64 {
+        if val.is_nan() { f64::NAN } else if val == 0.0 { 0.0 } else { val }
     }
 }
 
 impl Hash for FloatType {
     #[inline]
     fn hash<H>(&self, mut state: &mut H) where H: Hasher {        
-        let fbytearray = FloatType::integer_decode(self.value);
+        let fbytearray = FloatType::integer_decode(FloatType::canonical(self.value));
         fbytearray.hash(&mut state);
-        state.finish();
     }    
 }
 
@@ -71,7 +94,6 @@ impl Hash for StringType {
     #[inline]
     fn hash<H>(&self, mut state: &mut H) where H: Hasher {
         self.value.as_str().hash(&mut state);
-        state.finish();
     }    
 }
 
@@ -80,16 +102,17 @@ impl Hash for DataTypes {
     fn hash<H>(&self, mut state: &mut H) where H: Hasher {
         match self {
             DataTypes::FloatType(FloatType{value}) => {
-                let fbytearray = FloatType::integer_decode(*value);
+                let fbytearray = FloatType::integer_decode(FloatType::canonical(*value));
                 fbytearray.hash(&mut state);
-                state.finish();
             },
             DataTypes::IntegerType(IntegerType{value}) => {
                 value.hash(state);
             },
             DataTypes::StringType(StringType{value}) => {
                 value.as_str().hash(&mut state);
-                state.finish();
+            },
+            DataTypes::NullType(_) => {
+                0u8.hash(state);
             },
         }
     }    
@@ -100,8 +123,11 @@ pub enum DataTypes {
     FloatType(FloatType),
     IntegerType(IntegerType),
     StringType(StringType),
+    NullType(NullType),
 }
 
+static NULL_VALUE : DataTypes = DataTypes::NullType(NullType{});
+
 impl Clone for StringType {
     #[inline]
     fn clone(&self) -> StringType {
@@ -112,7 +138,7 @@ impl Clone for StringType {
 impl PartialEq for FloatType {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.value == other.value
+        self.value == other.value || (self.value.is_nan() && other.value.is_nan())
     }
 }
 
@@ -143,7 +169,7 @@ impl PartialEq for DataTypes {
 
         match operands {
             (DataTypes::FloatType(FloatType{value : lvalue}), DataTypes::FloatType(FloatType{value : rvalue})) => {
-                lvalue == rvalue
+                lvalue == rvalue || (lvalue.is_nan() && rvalue.is_nan())
             },
             (DataTypes::IntegerType(IntegerType{value : lvalue}), DataTypes::IntegerType(IntegerType{value : rvalue})) => {
                 lvalue == rvalue
@@ -151,6 +177,7 @@ impl PartialEq for DataTypes {
             (DataTypes::StringType(StringType{value : lvalue}), DataTypes::StringType(StringType{value : rvalue})) => {
                 lvalue == rvalue
             },
+            (DataTypes::NullType(_), DataTypes::NullType(_)) => { true },
             _ => false
         }
     }
@@ -158,12 +185,52 @@ impl PartialEq for DataTypes {
 
 impl Eq for DataTypes {}
 
+// total ordering used for sorting
+//
+// integers and floats compare numerically, NaN sorts after
+// every number, numbers sort before strings and nulls sort
+// last; an integer and a float holding the same number are
+// ordered integer first so that `Equal` implies `==`
+//
+impl Ord for DataTypes {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let operands = (self, other);
+
+        match operands {
+            (DataTypes::IntegerType(IntegerType{value : lvalue}), DataTypes::IntegerType(IntegerType{value : rvalue})) => {
+                lvalue.cmp(rvalue)
+            },
+            (DataTypes::FloatType(FloatType{value : lvalue}), DataTypes::FloatType(FloatType{value : rvalue})) => {
+                DataTypes::float_cmp(*lvalue, *rvalue)
+            },
+            (DataTypes::IntegerType(IntegerType{value : lvalue}), DataTypes::FloatType(FloatType{value : rvalue})) => {
+                DataTypes::float_cmp(*lvalue as f64, *rvalue).then(Ordering::Less)
+            },
+            (DataTypes::FloatType(FloatType{value : lvalue}), DataTypes::IntegerType(IntegerType{value : rvalue})) => {
+                DataTypes::float_cmp(*lvalue, *rvalue as f64).then(Ordering::Greater)
+            },
+            (DataTypes::StringType(StringType{value : lvalue}), DataTypes::StringType(StringType{value : rvalue})) => {
+                lvalue.cmp(rvalue)
+            },
+            _ => self.type_rank().cmp(&other.type_rank())
+        }
+    }
+}
+
+impl PartialOrd for DataTypes {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl DataTypes {
     pub fn println(&self) {
         match self {
             DataTypes::FloatType(FloatType{value}) => { println!("{}", value); }
             DataTypes::IntegerType(IntegerType{value})=> { println!("{}", value); }
             DataTypes::StringType(StringType{value})=> { println!("{}", value); }
+            DataTypes::NullType(_)=> { println!("null"); }
         }
     }
 
@@ -172,6 +239,7 @@ impl DataTypes {
             DataTypes::FloatType(FloatType{value}) => { print!("{}", value); }
             DataTypes::IntegerType(IntegerType{value})=> { print!("{}", value); }
             DataTypes::StringType(StringType{value})=> { print!("{}", value); }
+            DataTypes::NullType(_)=> { print!("null"); }
         }
     }
 
@@ -192,6 +260,7 @@ impl DataTypes {
                 value.as_str().hash(&mut s);
                 s.finish() as i64
             }
+            DataTypes::NullType(_) => { 0 }
         }
     }
 
@@ -200,373 +269,538 @@ impl DataTypes {
             DataTypes::FloatType(FloatType{value}) => { value.to_string() }
             DataTypes::IntegerType(IntegerType{value}) => { value.to_string() }
             DataTypes::StringType(StringType{value}) => { String::from(value) }
+            DataTypes::NullType(_) => { String::from("null") }
             //_ => { String::from("NOT A STRING") }
         }
     }
+
+    // the value converted to `to`, None when it does not convert;
+    // nulls stay null
+    //
+    fn cast(&self, to : ColumnType) -> Option<DataTypes> {
+        if self.is_null() {
+            return Some(DataTypes::null());
+        }
+
+        match (to, self) {
+            (ColumnType::Float, DataTypes::StringType(StringType{value})) => value.trim().parse::<f64>().ok().map(|v| DataTypes::FloatType(FloatType{value : v})),
+            (ColumnType::Float, _) => Some(DataTypes::FloatType(FloatType{value : self.fvalue()})),
+            (ColumnType::Integer, DataTypes::IntegerType(_)) => Some(self.clone()),
+            (ColumnType::Integer, DataTypes::FloatType(FloatType{value})) => {
+                if value.fract() == 0.0 && *value >= i64::MIN as f64 && *value < i64::MAX as f64 { Some(DataTypes::IntegerType(IntegerType{value : *value as i64})) } else { None }
+            },
+            (ColumnType::Integer, DataTypes::StringType(StringType{value})) => {
+                value.trim().parse::<i64>().ok().map(|v| DataTypes::IntegerType(IntegerType{value : v}))
+                    .or_else(|| DataTypes::FloatType(FloatType{value : value.trim().parse::<f64>().ok()?}).cast(ColumnType::Integer))
+            },
+            (ColumnType::String, _) => Some(DataTypes::StringType(StringType{value : self.svalue()})),
+            _ => None,
+        }
+    }
+
+    pub fn null() -> DataTypes {
+        DataTypes::NullType(NullType{})
+    }
+
+    pub fn is_null(&self) -> bool {
+        match self {
+            DataTypes::FloatType(FloatType{value}) => { value.is_nan() }
+            DataTypes::NullType(_) => { true }
+            _ => false
+        }
+    }
+
+    fn type_rank(&self) -> u8 {
+        match self {
+            DataTypes::IntegerType(_) | DataTypes::FloatType(_) => { 0 }
+            DataTypes::StringType(_) => { 1 }
+            DataTypes::NullType(_) => { 2 }
+        }
+    }
+
+    // seconds since 1970-01-01 for strings shaped like
+    // `YYYYMMDD[ HH:MM[:SS]]` or `YYYY-MM-DD[ |T]HH:MM[:SS]`
+    //
+    pub fn timestamp(&self) -> Option<i64> {
+        let value = match self {
+            DataTypes::StringType(StringType{value}) => { value.trim() }
+            _ => { return None; }
+        };
+
+        let (date, time) = match value.find([' ', 'T']) {
+            Some(pos) => (&value[..pos], value[pos+1..].trim()),
+            None => (value, ""),
+        };
+
+        let digits : String = date.chars().filter(|c| *c != '-').collect();
+        if digits.len() != 8 || !digits.chars().all(|c| c.is_ascii_digit()) || (date.contains('-') && date.len() != 10) {
+            return None;
+        }
+
+        let year : i64 = digits[0..4].parse().ok()?;
+        let month : i64 = digits[4..6].parse().ok()?;
+        let day : i64 = digits[6..8].parse().ok()?;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+
+        let mut seconds : i64 = 0;
+        if !time.is_empty() {
+            let parts : Vec<&str> = time.split(':').collect();
+            if parts.len() < 2 || parts.len() > 3 {
+                return None;
+            }
+
+            let hms : Vec<i64> = parts.iter().map(|p| p.parse::<i64>().ok()).collect::<Option<_>>()?;
+            seconds = hms[0] * 3600 + hms[1] * 60 + hms.get(2).unwrap_or(&0);
+        }
+
+        // days from civil, http://howardhinnant.github.io/date_algorithms.html
+        //
+        let y = if month <= 2 { year - 1 } else { year };
+        let era = if y >= 0 { y } else { y - 399 } / 400;
+        let yoe = y - era * 400;
+        let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146097 + doe - 719468;
+
+        Some(days * 86400 + seconds)
+    }
+
+    // numeric position of a key value; numbers map to themselves
+    // and datetime strings to their timestamp in seconds
+    //
+    fn key_value(&self) -> Option<f64> {
+        match self {
+            DataTypes::FloatType(_) | DataTypes::IntegerType(_) => if self.is_null() { None } else { Some(self.fvalue()) },
+            DataTypes::StringType(_) => { self.timestamp().map(|t| t as f64) }
+            DataTypes::NullType(_) => { None }
+        }
+    }
+
+    fn float_cmp(l : f64, r : f64) -> Ordering {
+        match (l.is_nan(), r.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => l.partial_cmp(&r).unwrap(),
+        }
+    }
 }
 
 trait CalculateColumn {
-    fn column(column : & Vec<DataTypes>, column_name : &str) -> DataFrame;
+    fn column(column : &[DataTypes], column_name : &str) -> DataFrame;
 }
 
 impl CalculateColumn for FloatType {   
-    fn column(columns : &Vec<DataTypes>, column_name : &str) -> DataFrame {
+    fn column(columns : &[DataTypes], column_name : &str) -> DataFrame {
         let cpy : Vec<DataTypes> = columns.iter().map(|i| {
             DataTypes::FloatType(FloatType{ value : i.fvalue() })
         }).collect();
 
-        return DataFrame{ labels : std::vec![column_name.to_string(),], columns : std::vec![cpy,] };
+        DataFrame{ labels : std::vec![column_name.to_string(),], columns : std::vec![cpy,] }
     }
 }
 
 impl CalculateColumn for IntegerType {
-    fn column(columns : &Vec<DataTypes>, column_name : &str) -> DataFrame {
+    fn column(columns : &[DataTypes], column_name : &str) -> DataFrame {
         let cpy : Vec<DataTypes> = columns.iter().map(|i| {
             DataTypes::IntegerType(IntegerType{ value : i.ivalue() })
         }).collect();
 
-        return DataFrame{ labels : std::vec![column_name.to_string(),], columns : std::vec![cpy,] };
+        DataFrame{ labels : std::vec![column_name.to_string(),], columns : std::vec![cpy,] }
     }
 }
 
 impl CalculateColumn for StringType {        
-    fn column(columns : &Vec<DataTypes>, column_name : &str) -> DataFrame {
+    fn column(columns : &[DataTypes], column_name : &str) -> DataFrame {
         let cpy : Vec<DataTypes> = columns.iter().map(|i| {
             DataTypes::FloatType(FloatType{ value : i.fvalue() })
         }).collect();
 
-        return DataFrame{ labels : std::vec![column_name.to_string(),], columns : std::vec![cpy,] };
+        DataFrame{ labels : std::vec![column_name.to_string(),], columns : std::vec![cpy,] }
     }
 }
 
+impl CalculateColumn for NullType {
+    fn column(columns : &[DataTypes], column_name : &str) -> DataFrame {
+        DataFrame{ labels : std::vec![column_name.to_string(),], columns : std::vec![columns.to_vec(),] }
+    }
+}
+
+// Kahan-Babuska (Neumaier) compensated summation, the running
+// compensation also recovers the low order bits lost when a term
+// is larger than the sum so far
+//
+fn compensated_sum<I : Iterator<Item = f64>>(values : I) -> f64 {
+    let (sum, compensation) = values.fold((0.0, 0.0), |(sum, c) : (f64, f64), x| {
+        let t = sum + x;
+        let c = if sum.abs() >= x.abs() { c + ((sum - t) + x) } else { c + ((x - t) + sum) };
+        (t, c)
+    });
+    sum + compensation
+}
+
 trait CalculateSum {
-    fn sum(column : & Vec<DataTypes>) -> DataTypes;
+    fn sum(column : &[DataTypes]) -> DataTypes;
+}
+
+// float and integer cells of a mixed column, nulls and strings are
+// skipped
+//
+fn numeric_cells(columns : &[DataTypes]) -> impl Iterator<Item = f64> + '_ {
+    columns.iter().filter(|i| matches!(i, DataTypes::FloatType(_) | DataTypes::IntegerType(_)) && !i.is_null()).map(|i| i.fvalue())
 }
 
 impl CalculateSum for FloatType {   
-    fn sum(columns : &Vec<DataTypes>) -> DataTypes {
-        let fvalue : f64 = columns.iter().fold(0.0, |sum, i| sum + match i {
-            DataTypes::FloatType(FloatType{value}) => { *value }
-            _ => 0.0
-        });
+    fn sum(columns : &[DataTypes]) -> DataTypes {
+        let fvalue : f64 = compensated_sum(numeric_cells(columns));
 
-        return DataTypes::FloatType(FloatType{value : fvalue});
+        DataTypes::FloatType(FloatType{value : fvalue})
     }
 }
 
+// accumulated in i128; a total outside the i64 range is promoted
+// to a float rather than wrapping
+//
 impl CalculateSum for IntegerType {
-    fn sum(columns : &Vec<DataTypes>) -> DataTypes {
-        let return_value : i64 = columns.iter().fold(0, |sum, i| sum + match i {
-            DataTypes::IntegerType(IntegerType{value}) => { *value } 
-            _ => 0
-        });
+    fn sum(columns : &[DataTypes]) -> DataTypes {
+        if IntegerType::has_floats(columns) {
+            return FloatType::sum(columns);
+        }
 
-        return DataTypes::IntegerType(IntegerType{value : return_value});
+        let return_value : i128 = IntegerType::values(columns).iter().map(|&v| v as i128).sum();
+
+        match i64::try_from(return_value) {
+            Ok(value) => DataTypes::IntegerType(IntegerType{value}),
+            Err(_) => DataTypes::FloatType(FloatType{value : return_value as f64}),
+        }
     }
 }
 
 impl CalculateSum for StringType {        
-    fn sum(_ : &Vec<DataTypes>) -> DataTypes {
-        return DataTypes::StringType(StringType{value : String::from("NAN")})
+    fn sum(_ : &[DataTypes]) -> DataTypes {
+        DataTypes::StringType(StringType{value : String::from("NAN")})
+    }
+}
+
+impl CalculateSum for NullType {
+    fn sum(_ : &[DataTypes]) -> DataTypes {
+        DataTypes::null()
     }
 }
 
 trait CalculateMean {
-    fn mean(column : & Vec<DataTypes>) -> DataTypes;
+    fn mean(column : &[DataTypes]) -> DataTypes;
 }
 
 impl CalculateMean for FloatType {   
-    fn mean(columns : &Vec<DataTypes>) -> DataTypes {
-        let fvalue : f64 = columns.iter().fold(0.0, |sum, i| sum + match i {
-            DataTypes::FloatType(FloatType{value}) => { *value }
-            _ => 0.0
-        });
+    fn mean(columns : &[DataTypes]) -> DataTypes {
+        let values : Vec<f64> = numeric_cells(columns).collect();
+
+        DataTypes::FloatType(FloatType{value : compensated_sum(values.iter().cloned()) / values.len() as f64})
+    }
+}
+
+impl IntegerType {
+
+    // integer values of a column, anything else is skipped
+    //
+    fn values(columns : &[DataTypes]) -> Vec<i64> {
+        columns.iter().filter_map(|i| match i {
+            DataTypes::IntegerType(IntegerType{value}) => { Some(*value) }
+            _ => None
+        }).collect()
+    }
+
+    // a float cell anywhere in the column makes its sum and mean float
+    // ones over `numeric_cells`, whichever cell comes first
+    //
+    fn has_floats(columns : &[DataTypes]) -> bool {
+        columns.iter().any(|i| matches!(i, DataTypes::FloatType(_)) && !i.is_null())
+    }
 
-        return DataTypes::FloatType(FloatType{value : fvalue / columns.len() as f64});
+    // the sum is accumulated in i128 so it can not overflow
+    //
+    fn fmean(values : &[i64]) -> f64 {
+        let sum : i128 = values.iter().map(|&v| v as i128).sum();
+        sum as f64 / values.len() as f64
     }
 }
 
 impl CalculateMean for IntegerType {
-    fn mean(columns : &Vec<DataTypes>) -> DataTypes {
-        let return_value : i64 = columns.iter().fold(0, |sum, i| sum + match i {
-            DataTypes::IntegerType(IntegerType{value}) => { *value } 
-            _ => 0
-        });
+    fn mean(columns : &[DataTypes]) -> DataTypes {
+        if IntegerType::has_floats(columns) {
+            return FloatType::mean(columns);
+        }
 
-        return DataTypes::IntegerType(IntegerType{value : return_value / columns.len() as i64});
+        let values = IntegerType::values(columns);
+        DataTypes::FloatType(FloatType{value : IntegerType::fmean(&values)})
     }
 }
 
 impl CalculateMean for StringType {        
-    fn mean(_ : &Vec<DataTypes>) -> DataTypes {
-        return DataTypes::StringType(StringType{value : String::from("NAN")})
+    fn mean(_ : &[DataTypes]) -> DataTypes {
+        DataTypes::StringType(StringType{value : String::from("NAN")})
+    }
+}
+
+impl CalculateMean for NullType {
+    fn mean(_ : &[DataTypes]) -> DataTypes {
+        DataTypes::null()
     }
 }
 
+// variance and standard deviation divide the sum of squared
+// deviations by `n - ddof`; 0 gives the population and 1 the
+// sample estimator. the sums come from the single pass Welford
+// accumulator in `Moments`
+//
 trait CalculateStdDev {
-    fn stddev(column : & Vec<DataTypes>) -> DataTypes;
+    fn stddev(column : &[DataTypes], ddof : usize) -> DataTypes;
 }
 
 impl CalculateStdDev for FloatType {   
-    fn stddev(columns : &Vec<DataTypes>) -> DataTypes {
-        let mean_value : f64 = FloatType::mean(&columns).fvalue();
-
-        let return_value : f64 = columns.iter().fold(0.0, |sum, i| sum + match i {
-            DataTypes::FloatType(FloatType{value}) => { (*value - mean_value).powf(2.0) } 
-            _ => 0.0
-        });
-
-        let stddev_value : f64 = (return_value / (columns.len() as f64)).sqrt();
-
-        return DataTypes::FloatType(FloatType{value : stddev_value});        
+    fn stddev(columns : &[DataTypes], ddof : usize) -> DataTypes {
+        DataTypes::FloatType(FloatType{value : Moments::new(columns).variance(ddof).sqrt()})
     }
 }
 
 impl CalculateStdDev for IntegerType {
-    fn stddev(columns : &Vec<DataTypes>) -> DataTypes {
-        let mean_value : i64 = IntegerType::mean(&columns).ivalue();
-
-        let return_value : i64 = columns.iter().fold(0, |sum, i| sum + match i {
-            DataTypes::IntegerType(IntegerType{value}) => { (*value - mean_value).pow(2) } 
-            _ => 0
-        });
-
-        let stddev_value : i64 = (( return_value / ( columns.len() as i64)) as f64).sqrt() as i64;
-
-        return DataTypes::IntegerType(IntegerType{value : stddev_value});
+    fn stddev(columns : &[DataTypes], ddof : usize) -> DataTypes {
+        DataTypes::FloatType(FloatType{value : Moments::new(columns).variance(ddof).sqrt()})
     }
 }
 
 impl CalculateStdDev for StringType {        
-    fn stddev(_ : &Vec<DataTypes>) -> DataTypes {
-        return DataTypes::StringType(StringType{value : String::from("NAN")})
+    fn stddev(_ : &[DataTypes], _ : usize) -> DataTypes {
+        DataTypes::StringType(StringType{value : String::from("NAN")})
     }
 }
 
-trait CalculatePStdDev {
-    fn pstddev(column : & Vec<DataTypes>) -> DataTypes;
+impl CalculateStdDev for NullType {
+    fn stddev(_ : &[DataTypes], _ : usize) -> DataTypes {
+        DataTypes::null()
+    }
 }
 
-impl CalculatePStdDev for FloatType {   
-    fn pstddev(columns : &Vec<DataTypes>) -> DataTypes {
-        let mean_value : f64 = FloatType::mean(&columns).fvalue();
-        let denom : f64 = columns.len() as f64;
-
-        let return_value : f64 = columns.iter().fold(0.0, |sum, i| sum + match i {
-            DataTypes::FloatType(FloatType{value}) => { (*value - mean_value).powf(2.0) / denom } 
-            _ => 0.0
-        });
+trait CalculateVariance {
+    fn variance(column : &[DataTypes], ddof : usize) -> DataTypes;
+}
 
-        return DataTypes::FloatType(FloatType{value : return_value.sqrt()});        
+impl CalculateVariance for FloatType {   
+    fn variance(columns : &[DataTypes], ddof : usize) -> DataTypes {
+        DataTypes::FloatType(FloatType{value : Moments::new(columns).variance(ddof)})
     }
 }
 
-impl CalculatePStdDev for IntegerType {
-    fn pstddev(columns : &Vec<DataTypes>) -> DataTypes {
-        let mean_value : i64 = IntegerType::mean(&columns).ivalue();
-        let denom : i64 = columns.len() as i64;
-
-        let return_value : i64 = columns.iter().fold(0, |sum, i| sum + match i {
-            DataTypes::IntegerType(IntegerType{value}) => { (*value - mean_value).pow(2) / denom } 
-            _ => 0
-        });
+impl CalculateVariance for IntegerType {
+    fn variance(columns : &[DataTypes], ddof : usize) -> DataTypes {
+        DataTypes::FloatType(FloatType{value : Moments::new(columns).variance(ddof)})
+    }
+}
 
-        return DataTypes::IntegerType(IntegerType{value : (return_value as f64).sqrt() as i64});
+impl CalculateVariance for StringType {        
+    fn variance(_ : &[DataTypes], _ : usize) -> DataTypes {
+        DataTypes::StringType(StringType{value : String::from("NAN")})
     }
 }
 
-impl CalculatePStdDev for StringType {        
-    fn pstddev(_ : &Vec<DataTypes>) -> DataTypes {
-        return DataTypes::StringType(StringType{value : String::from("NAN")})
+impl CalculateVariance for NullType {
+    fn variance(_ : &[DataTypes], _ : usize) -> DataTypes {
+        DataTypes::null()
     }
 }
 
-trait CalculateVariance {
-    fn variance(column : & Vec<DataTypes>) -> DataTypes;
+// rolling statistics
+//
+// one value per full window of `window` rows. the window's mean and
+// sum of squared deviations are slid along with Welford's update,
+// adding the value entering and removing the value leaving; nulls
+// are left out of the window's count
+//
+// https://jonisalonen.com/2014/efficient-and-accurate-rolling-standard-deviation/
+//
+#[derive(Copy, Clone, Default)]
+struct RollingWindow {
+    n : usize,
+    mean : f64,
+    m2 : f64,
 }
 
-impl CalculateVariance for FloatType {   
-    fn variance(columns : &Vec<DataTypes>) -> DataTypes {
-        let mean_value : f64 = FloatType::mean(&columns).fvalue();
-        let denom : f64 = columns.len() as f64;
+impl RollingWindow {
 
-        let return_value : f64 = columns.iter().fold(0.0, |sum, i| sum + match i {
-            DataTypes::FloatType(FloatType{value}) => { (*value - mean_value).powf(2.0) } 
-            _ => 0.0
-        });
+    fn add(&mut self, x : &DataTypes) {
+        if x.is_null() {
+            return;
+        }
 
-        return DataTypes::FloatType(FloatType{value : return_value / denom});        
+        let x = x.fvalue();
+        self.n += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.n as f64;
+        self.m2 += delta * (x - self.mean);
     }
-}
 
-impl CalculateVariance for IntegerType {
-    fn variance(columns : &Vec<DataTypes>) -> DataTypes {
-        let mean_value : i64 = IntegerType::mean(&columns).ivalue();
-        let denom : i64 = columns.len() as i64;
+    fn remove(&mut self, x : &DataTypes) {
+        if x.is_null() {
+            return;
+        }
 
-        let return_value : i64 = columns.iter().fold(0, |sum, i| sum + match i {
-            DataTypes::IntegerType(IntegerType{value}) => { (*value - mean_value).pow(2) } 
-            _ => 0
-        });
+        let x = x.fvalue();
+        self.n -= 1;
+        if self.n == 0 {
+            *self = RollingWindow::default();
+            return;
+        }
 
-        return DataTypes::IntegerType(IntegerType{value : return_value / denom });
+        let delta = x - self.mean;
+        self.mean -= delta / self.n as f64;
+        self.m2 = (self.m2 - delta * (x - self.mean)).max(0.0);
     }
-}
 
-impl CalculateVariance for StringType {        
-    fn variance(_ : &Vec<DataTypes>) -> DataTypes {
-        return DataTypes::StringType(StringType{value : String::from("NAN")})
+    // applies `stat` to every full window
+    //
+    fn slide(columns : &[DataTypes], window : usize, stat : &dyn Fn(&RollingWindow) -> f64) -> Vec<DataTypes> {
+        let mut state = RollingWindow::default();
+        let mut values : Vec<DataTypes> = Vec::new();
+
+        if window == 0 {
+            return values;
+        }
+
+        for (i, x) in columns.iter().enumerate() {
+            state.add(x);
+            if i >= window {
+                state.remove(&columns[i - window]);
+            }
+            if i + 1 >= window {
+                values.push(DataTypes::FloatType(FloatType{ value : stat(&state) }));
+            }
+        }
+
+        values
+    }
+
+    fn variance(&self, ddof : usize) -> f64 {
+        if self.n <= ddof { f64::NAN } else { self.m2 / (self.n - ddof) as f64 }
     }
 }
 
-// rolling statistics
-//
-// https://jonisalonen.com/2014/efficient-and-accurate-rolling-standard-deviation/
-//
 trait CalculateSimpleRollingMean {
-    fn simple_rolling_mean(column : & Vec<DataTypes>, window : usize) -> Vec<DataTypes>;
+    fn simple_rolling_mean(column : &[DataTypes], window : usize) -> Vec<DataTypes>;
 }
 
 impl CalculateSimpleRollingMean for FloatType {   
-    fn simple_rolling_mean(columns : &Vec<DataTypes>, window : usize) -> Vec<DataTypes> {
-        let fwin : f64 = window as f64;
-        let mut rolling_avg : f64 = 0.0;
-        columns.windows(window).zip(columns.windows(window).skip(1)).map(
-            |(x, y)| DataTypes::FloatType(FloatType{ value : x.iter().zip(y).fold(rolling_avg, |acc, (a, b)| {
-                    rolling_avg = (acc + (a.fvalue() - b.fvalue())) / fwin;
-                    rolling_avg
-                })
-            })
-        ).collect()
+    fn simple_rolling_mean(columns : &[DataTypes], window : usize) -> Vec<DataTypes> {
+        RollingWindow::slide(columns, window, &|w| if w.n == 0 { f64::NAN } else { w.mean })
     }
 }
 
 impl CalculateSimpleRollingMean for IntegerType {
-    fn simple_rolling_mean(columns : &Vec<DataTypes>, window : usize) -> Vec<DataTypes> {
-        let iwin : i64 = window as i64;
-        let mut rolling_avg : i64 = 0;
-        columns.windows(window).zip(columns.windows(window).skip(1)).map(
-            |(x, y)| DataTypes::IntegerType(IntegerType{ value : x.iter().zip(y).fold(rolling_avg, |acc, (a, b)| {
-                    rolling_avg = (acc + (a.ivalue() - b.ivalue())) / iwin;
-                    rolling_avg
-                })
-            })
-        ).collect()
+    fn simple_rolling_mean(columns : &[DataTypes], window : usize) -> Vec<DataTypes> {
+        FloatType::simple_rolling_mean(columns, window)
     }
 }
 
 impl CalculateSimpleRollingMean for StringType {        
-    fn simple_rolling_mean(columns: &Vec<DataTypes>, window : usize) -> Vec<DataTypes> {
+    fn simple_rolling_mean(_columns: &[DataTypes], window : usize) -> Vec<DataTypes> {
+        if window == 0 {
+            return Vec::new();
+        }
+
         vec![DataTypes::StringType(StringType{value : String::from("NAN")});1]
     }
 }
 
+impl CalculateSimpleRollingMean for NullType {
+    fn simple_rolling_mean(columns: &[DataTypes], window : usize) -> Vec<DataTypes> {
+        if window == 0 {
+            return Vec::new();
+        }
+
+        columns.windows(window).map(|_| DataTypes::null()).collect()
+    }
+}
+
 trait CalculateRollingStdDev {
-    fn rolling_stddev(column : & Vec<DataTypes>, window : usize) -> Vec<DataTypes>;
+    fn rolling_stddev(column : &[DataTypes], window : usize, ddof : usize) -> Vec<DataTypes>;
 }
 
 impl CalculateRollingStdDev for FloatType {   
-    fn rolling_stddev(columns : &Vec<DataTypes>, window : usize) -> Vec<DataTypes> {
-        let fwin : f64 = window as f64;
-        let fwinmo : f64 = fwin - 1.0;
-
-        let mut rolling_avg : f64 = 0.0;
-        let mut rolling_var : f64 = 0.0;
-
-        columns.windows(window).zip(columns.windows(window).skip(1)).map(
-            |(x, y)| DataTypes::FloatType(
-                FloatType{ value : x.iter().zip(y).fold((0.0, rolling_avg, rolling_var, 0.0), |(nav, oav, var, stdev), (a, b)| {
-                    rolling_avg = oav + ((a.fvalue() - b.fvalue()) / fwin);
-                    rolling_var = ((b.fvalue() - a.fvalue()) * (b.fvalue() - rolling_avg + a.fvalue() - oav ) / fwinmo) + var;
-                    (rolling_avg, oav, rolling_var, rolling_var.sqrt())
-                }).3
-            })
-        ).collect()
+    fn rolling_stddev(columns : &[DataTypes], window : usize, ddof : usize) -> Vec<DataTypes> {
+        RollingWindow::slide(columns, window, &|w| w.variance(ddof).sqrt())
     }
 }
 
 impl CalculateRollingStdDev for IntegerType {
-    fn rolling_stddev(columns : &Vec<DataTypes>, window : usize) -> Vec<DataTypes> {
-        let iwin : i64 = window as i64;
-        let iwinmo : i64 = iwin - 1;
-
-        let mut rolling_avg : i64 = 0;
-        let mut rolling_var : i64 = 0;
-
-        columns.windows(window).zip(columns.windows(window).skip(1)).map(
-            |(x, y)| DataTypes::IntegerType(
-                IntegerType{ value : x.iter().zip(y).fold((0, rolling_avg, rolling_var, 0), |(nav, oav, var, stdev), (a, b)| {
-                    rolling_avg = oav + ((a.ivalue() - b.ivalue()) / iwin);
-                    rolling_var = ((b.ivalue() - a.ivalue()) * (b.ivalue() - rolling_avg + a.ivalue() - oav ) / iwinmo) + var;
-                    (rolling_avg, oav, rolling_var, (rolling_var as f64).sqrt() as i64)
-                }).3
-            })
-        ).collect()
+    fn rolling_stddev(columns : &[DataTypes], window : usize, ddof : usize) -> Vec<DataTypes> {
+        FloatType::rolling_stddev(columns, window, ddof)
     }
 }
 
 impl CalculateRollingStdDev for StringType {        
-    fn rolling_stddev(columns: &Vec<DataTypes>, window : usize) -> Vec<DataTypes> {
+    fn rolling_stddev(_columns: &[DataTypes], window : usize, _ddof : usize) -> Vec<DataTypes> {
+        if window == 0 {
+            return Vec::new();
+        }
+
         vec![DataTypes::StringType(StringType{value : String::from("NAN")});1]
     }
 }
 
+impl CalculateRollingStdDev for NullType {
+    fn rolling_stddev(columns: &[DataTypes], window : usize, _ddof : usize) -> Vec<DataTypes> {
+        if window == 0 {
+            return Vec::new();
+        }
+
+        columns.windows(window).map(|_| DataTypes::null()).collect()
+    }
+}
+
 trait CalculateRollingVariance {
-    fn rolling_variance(column : & Vec<DataTypes>, window : usize) -> Vec<DataTypes>;
+    fn rolling_variance(column : &[DataTypes], window : usize, ddof : usize) -> Vec<DataTypes>;
 }
 
 impl CalculateRollingVariance for FloatType {   
-    fn rolling_variance(columns : &Vec<DataTypes>, window : usize) -> Vec<DataTypes> {
-        let fwin : f64 = window as f64;
-        let fwinmo : f64 = fwin - 1.0;
-
-        let mut rolling_avg : f64 = 0.0;
-        let mut rolling_var : f64 = 0.0;
-
-        columns.windows(window).zip(columns.windows(window).skip(1)).map(
-            |(x, y)| DataTypes::FloatType(
-                FloatType{ value : x.iter().zip(y).fold((0.0, rolling_avg, rolling_var), |(nav, oav, var), (a, b)| {
-                    rolling_avg = oav + ((a.fvalue() - b.fvalue()) / fwin);
-                    rolling_var = ((b.fvalue() - a.fvalue()) * (b.fvalue() - rolling_avg + a.fvalue() - oav ) / fwinmo) + var;
-                    (rolling_avg, oav, rolling_var)
-                }).2
-            })
-        ).collect()
+    fn rolling_variance(columns : &[DataTypes], window : usize, ddof : usize) -> Vec<DataTypes> {
+        RollingWindow::slide(columns, window, &|w| w.variance(ddof))
     }
 }
 
 impl CalculateRollingVariance for IntegerType {
-    fn rolling_variance(columns : &Vec<DataTypes>, window : usize) -> Vec<DataTypes> {
-        let iwin : i64 = window as i64;
-        let iwinmo : i64 = iwin - 1;
-
-        let mut rolling_avg : i64 = 0;
-        let mut rolling_var : i64 = 0;
-
-        columns.windows(window).zip(columns.windows(window).skip(1)).map(
-            |(x, y)| DataTypes::IntegerType(
-                IntegerType{ value : x.iter().zip(y).fold((0, rolling_avg, rolling_var), |(nav, oav, var), (a, b)| {
-                    rolling_avg = oav + ((a.ivalue() - b.ivalue()) / iwin);
-                    rolling_var = ((b.ivalue() - a.ivalue()) * (b.ivalue() - rolling_avg + a.ivalue() - oav ) / iwinmo) + var;
-                    (rolling_avg, oav, rolling_var)
-                }).2
-            })
-        ).collect()
+    fn rolling_variance(columns : &[DataTypes], window : usize, ddof : usize) -> Vec<DataTypes> {
+        FloatType::rolling_variance(columns, window, ddof)
     }
 }
 
 impl CalculateRollingVariance for StringType {        
-    fn rolling_variance(columns: &Vec<DataTypes>, window : usize) -> Vec<DataTypes> {
+    fn rolling_variance(_columns: &[DataTypes], window : usize, _ddof : usize) -> Vec<DataTypes> {
+        if window == 0 {
+            return Vec::new();
+        }
+
         vec![DataTypes::StringType(StringType{value : String::from("NAN")});1]
     }
 }
 
+impl CalculateRollingVariance for NullType {
+    fn rolling_variance(columns: &[DataTypes], window : usize, _ddof : usize) -> Vec<DataTypes> {
+        if window == 0 {
+            return Vec::new();
+        }
+
+        columns.windows(window).map(|_| DataTypes::null()).collect()
+    }
+}
+
 trait CalculateDiff {
-    fn diff(column : & Vec<DataTypes>) -> Vec<DataTypes>;
+    fn diff(column : &[DataTypes]) -> Vec<DataTypes>;
 }
 
 impl CalculateDiff for FloatType {   
-    fn diff(columns : &Vec<DataTypes>) -> Vec<DataTypes> {
+    fn diff(columns : &[DataTypes]) -> Vec<DataTypes> {
         columns.iter().zip(columns.iter().skip(1)).map(
             |(x, y)| DataTypes::FloatType(FloatType{ value : (y.fvalue()-x.fvalue()) })
         ).collect()
@@ -574,7 +808,7 @@ impl CalculateDiff for FloatType {
 }
 
 impl CalculateDiff for IntegerType {
-    fn diff(columns : &Vec<DataTypes>) -> Vec<DataTypes> {
+    fn diff(columns : &[DataTypes]) -> Vec<DataTypes> {
         columns.iter().zip(columns.iter().skip(1)).map(
             |(x, y)| DataTypes::FloatType(FloatType{ value : (y.fvalue()-x.fvalue()) })
         ).collect()
@@ -582,19 +816,25 @@ impl CalculateDiff for IntegerType {
 }
 
 impl CalculateDiff for StringType {        
-    fn diff(columns: &Vec<DataTypes>) -> Vec<DataTypes> {
+    fn diff(columns: &[DataTypes]) -> Vec<DataTypes> {
         columns.iter().zip(columns.iter().skip(1)).map(
             |(x, y)| DataTypes::FloatType(FloatType{value : (y.svalue().len() as f64 - x.svalue().len() as f64) })
         ).collect()
     }
 }
 
+impl CalculateDiff for NullType {
+    fn diff(columns: &[DataTypes]) -> Vec<DataTypes> {
+        columns.iter().skip(1).map(|_| DataTypes::null()).collect()
+    }
+}
+
 trait CalculatePctChange {
-    fn pct_change(column : & Vec<DataTypes>) -> Vec<DataTypes>;
+    fn pct_change(column : &[DataTypes]) -> Vec<DataTypes>;
 }
 
 impl CalculatePctChange for FloatType {   
-    fn pct_change(columns : &Vec<DataTypes>) -> Vec<DataTypes> {
+    fn pct_change(columns : &[DataTypes]) -> Vec<DataTypes> {
         columns.iter().zip(columns.iter().skip(1)).map(
             |(x, y)| DataTypes::FloatType(FloatType{value : (y.fvalue()-x.fvalue())/x.fvalue()})
         ).collect()
@@ -602,7 +842,7 @@ impl CalculatePctChange for FloatType {
 }
 
 impl CalculatePctChange for IntegerType {
-    fn pct_change(columns : &Vec<DataTypes>) -> Vec<DataTypes> {
+    fn pct_change(columns : &[DataTypes]) -> Vec<DataTypes> {
         columns.iter().zip(columns.iter().skip(1)).map(
             |(x, y)| DataTypes::FloatType(FloatType{value : (y.fvalue()-x.fvalue())/x.fvalue()})
         ).collect()
@@ -610,542 +850,5464 @@ impl CalculatePctChange for IntegerType {
 }
 
 impl CalculatePctChange for StringType {        
-    fn pct_change(columns: &Vec<DataTypes>) -> Vec<DataTypes> {
+    fn pct_change(columns: &[DataTypes]) -> Vec<DataTypes> {
         columns.iter().zip(columns.iter().skip(1)).map(
             |(x, y)| DataTypes::FloatType(FloatType{value : (y.svalue().len() as f64 - x.svalue().len() as f64)/x.svalue().len() as f64 })
         ).collect()
     }
 }
 
-trait CalculateCompare {
-    fn cmp(r : &DataTypes, l : &DataTypes) -> bool;
+impl CalculatePctChange for NullType {
+    fn pct_change(columns: &[DataTypes]) -> Vec<DataTypes> {
+        columns.iter().skip(1).map(|_| DataTypes::null()).collect()
+    }
+}
+
+// order statistics
+//
+// nulls (and NaN) are skipped; quantiles use selection instead
+// of sorting the whole column
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum QuantileInterpolation {
+    Linear,
+    Lower,
+    Higher,
+    Nearest,
+    Midpoint,
 }
 
-impl CalculateCompare for FloatType {   
-    fn cmp(r : &DataTypes, l : &DataTypes) -> bool {
-        return r.fvalue() == l.fvalue()
+// quantile `q` of `values` by partial selection, reorders `values`;
+// parity is tested with `%` as `is_multiple_of` needs rust 1.87
+//
+#[allow(clippy::manual_is_multiple_of)]
+fn select_quantile(values : &mut [f64], q : f64, interpolation : QuantileInterpolation) -> f64 {
+    if values.is_empty() {
+        return f64::NAN;
     }
-}
 
-impl CalculateCompare for IntegerType {
-    fn cmp(r : &DataTypes, l : &DataTypes) -> bool {
-        return r.ivalue() == l.ivalue()
+    let pos = q * (values.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let frac = pos - lo as f64;
+
+    let (_, lo_value, upper) = values.select_nth_unstable_by(lo, |l, r| l.total_cmp(r));
+    let lo_value = *lo_value;
+    let hi_value = if frac > 0.0 { upper.iter().cloned().fold(f64::INFINITY, f64::min) } else { lo_value };
+
+    match interpolation {
+        QuantileInterpolation::Linear => { lo_value + (hi_value - lo_value) * frac },
+        QuantileInterpolation::Lower => { lo_value },
+        QuantileInterpolation::Higher => { hi_value },
+        QuantileInterpolation::Midpoint => { (lo_value + hi_value) / 2.0 },
+        QuantileInterpolation::Nearest => {
+            // ties go to the even position, as numpy does
+            if frac < 0.5 || (frac == 0.5 && lo % 2 == 0) { lo_value } else { hi_value }
+        },
     }
 }
 
-impl CalculateCompare for StringType {        
-    fn cmp(r : &DataTypes, l : &DataTypes) -> bool {
-        return r.svalue() == l.svalue()
+fn check_quantile(q : f64) -> Result<(), DataFrameError> {
+    if !(0.0..=1.0).contains(&q) {
+        return Err(DataFrameError::InvalidArgument(format!("quantile {} is not in [0, 1]", q)));
     }
+    Ok(())
 }
 
-type Series = Vec<DataTypes>;
-
-pub struct DataFrame {
-    labels : Vec<String>,
-    columns : Vec<Series>,
+fn present_values(columns : &[DataTypes]) -> Vec<f64> {
+    numeric_cells(columns).collect()
 }
 
-impl DataFrame {
+trait CalculateMin {
+    fn min(column : &[DataTypes]) -> DataTypes;
+}
 
-    pub fn new() -> DataFrame {
-        DataFrame{labels : Vec::new() , columns : Vec::new() }
+impl CalculateMin for FloatType {
+    fn min(columns : &[DataTypes]) -> DataTypes {
+        columns.iter().filter(|v| !v.is_null()).min().cloned().unwrap_or_else(DataTypes::null)
     }
+}
 
-    pub fn load_csv(&mut self, path : &Path) {
-        let path_str : String = (*path.to_str().unwrap()).to_string();
-        let contents : String = read_to_string(path_str).unwrap();
-        let mut split_contents : std::str::Lines = contents.lines();
-        let header = split_contents.next().unwrap();
-        
-        let fields : Vec<&str> = header.split(',').collect();
-        for field in &fields {
-            self.labels.push(String::from(*field));
-        }
+impl CalculateMin for IntegerType {
+    fn min(columns : &[DataTypes]) -> DataTypes {
+        columns.iter().filter(|v| !v.is_null()).min().cloned().unwrap_or_else(DataTypes::null)
+    }
+}
 
-        let fields_len = &fields.len();
-        self.columns = (0..*fields_len).map(|_x| Vec::new() ).collect();
+impl CalculateMin for StringType {
+    fn min(columns : &[DataTypes]) -> DataTypes {
+        columns.iter().filter(|v| !v.is_null()).min().cloned().unwrap_or_else(DataTypes::null)
+    }
+}
 
-        for ln in split_contents {
-            let values : Vec<&str> = ln.split(',').collect();
-            for (x, y) in (0..values.len()).zip(values) {
-                let fvalue = y.parse::<f64>();
-                let ivalue = y.parse::<i64>();
-                if fvalue.is_ok() {
-                    self.columns[x].push(DataTypes::FloatType(FloatType{value : fvalue.unwrap()}));
-                }
-                else if ivalue.is_ok() {
-                    self.columns[x].push(DataTypes::IntegerType(IntegerType{value : ivalue.unwrap()}));
-                }
-                else {
-                    self.columns[x].push(DataTypes::StringType(StringType{value : String::from(y)}));
-                }
-           }
-        }
+impl CalculateMin for NullType {
+    fn min(_ : &[DataTypes]) -> DataTypes {
+        DataTypes::null()
     }
+}
 
-    pub fn load_data(&mut self, data : & Vec<(&str, Series)>) {
-        self.labels.resize(data.len(), "".to_string());
-        self.columns.resize(data.len(), Series::new());
+trait CalculateMax {
+    fn max(column : &[DataTypes]) -> DataTypes;
+}
 
-        for x in 0..data.len() {
-            self.labels[x] = data[x].0.to_string();
-            self.columns[x] = data[x].1.clone();
-        }
+impl CalculateMax for FloatType {
+    fn max(columns : &[DataTypes]) -> DataTypes {
+        columns.iter().filter(|v| !v.is_null()).max().cloned().unwrap_or_else(DataTypes::null)
     }
+}
 
-    pub fn add_column(&mut self, data : &(&str, Series)) {
-        self.labels.push(data.0.to_string());
-        self.columns.push(data.1.clone());
+impl CalculateMax for IntegerType {
+    fn max(columns : &[DataTypes]) -> DataTypes {
+        columns.iter().filter(|v| !v.is_null()).max().cloned().unwrap_or_else(DataTypes::null)
     }
+}
 
-    pub fn get_column_index(&self, column_name : &str) -> usize {
-        self.labels.iter().position(|l| *l == column_name ).unwrap()
+impl CalculateMax for StringType {
+    fn max(columns : &[DataTypes]) -> DataTypes {
+        columns.iter().filter(|v| !v.is_null()).max().cloned().unwrap_or_else(DataTypes::null)
     }
+}
 
-    pub fn column_is_integer(&self, idx : usize) -> bool {
-        match self.columns[idx].first().unwrap() {
-            DataTypes::IntegerType(IntegerType{value : _}) => { true },
-            _ => false
-        }
+impl CalculateMax for NullType {
+    fn max(_ : &[DataTypes]) -> DataTypes {
+        DataTypes::null()
     }
+}
 
-    pub fn column_is_float(&self, idx : usize) -> bool {
-        match self.columns[idx].first().unwrap() {
-            DataTypes::FloatType(FloatType{value : _}) => { true },
-            _ => false
-        }
+// position of the first minimum/maximum, None when every value is null
+//
+trait CalculateArgMin {
+    fn argmin(column : &[DataTypes]) -> Option<usize>;
+}
+
+impl CalculateArgMin for FloatType {
+    fn argmin(columns : &[DataTypes]) -> Option<usize> {
+        columns.iter().enumerate().filter(|(_, v)| !v.is_null()).min_by(|l, r| l.1.cmp(r.1)).map(|(i, _)| i)
     }
+}
 
-    pub fn column_is_string(&self, idx : usize) -> bool {
-        match self.columns[idx].first().unwrap() {
-            DataTypes::StringType(StringType{value : _}) => { true },
-            _ => false
-        }
+impl CalculateArgMin for IntegerType {
+    fn argmin(columns : &[DataTypes]) -> Option<usize> {
+        columns.iter().enumerate().filter(|(_, v)| !v.is_null()).min_by(|l, r| l.1.cmp(r.1)).map(|(i, _)| i)
+    }
+}
+
+impl CalculateArgMin for StringType {
+    fn argmin(columns : &[DataTypes]) -> Option<usize> {
+        columns.iter().enumerate().filter(|(_, v)| !v.is_null()).min_by(|l, r| l.1.cmp(r.1)).map(|(i, _)| i)
+    }
+}
+
+impl CalculateArgMin for NullType {
+    fn argmin(_ : &[DataTypes]) -> Option<usize> {
+        None
+    }
+}
+
+trait CalculateArgMax {
+    fn argmax(column : &[DataTypes]) -> Option<usize>;
+}
+
+impl CalculateArgMax for FloatType {
+    fn argmax(columns : &[DataTypes]) -> Option<usize> {
+        columns.iter().enumerate().filter(|(_, v)| !v.is_null()).max_by(|l, r| l.1.cmp(r.1).then(r.0.cmp(&l.0))).map(|(i, _)| i)
+    }
+}
+
+impl CalculateArgMax for IntegerType {
+    fn argmax(columns : &[DataTypes]) -> Option<usize> {
+        columns.iter().enumerate().filter(|(_, v)| !v.is_null()).max_by(|l, r| l.1.cmp(r.1).then(r.0.cmp(&l.0))).map(|(i, _)| i)
+    }
+}
+
+impl CalculateArgMax for StringType {
+    fn argmax(columns : &[DataTypes]) -> Option<usize> {
+        columns.iter().enumerate().filter(|(_, v)| !v.is_null()).max_by(|l, r| l.1.cmp(r.1).then(r.0.cmp(&l.0))).map(|(i, _)| i)
+    }
+}
+
+impl CalculateArgMax for NullType {
+    fn argmax(_ : &[DataTypes]) -> Option<usize> {
+        None
+    }
+}
+
+trait CalculateQuantile {
+    fn quantile(column : &[DataTypes], q : f64, interpolation : QuantileInterpolation) -> DataTypes;
+}
+
+impl CalculateQuantile for FloatType {
+    fn quantile(columns : &[DataTypes], q : f64, interpolation : QuantileInterpolation) -> DataTypes {
+        let mut values = present_values(columns);
+        if values.is_empty() {
+            return DataTypes::null();
+        }
+
+        DataTypes::FloatType(FloatType{value : select_quantile(&mut values, q, interpolation)})
+    }
+}
+
+impl CalculateQuantile for IntegerType {
+    fn quantile(columns : &[DataTypes], q : f64, interpolation : QuantileInterpolation) -> DataTypes {
+        let mut values = present_values(columns);
+        if values.is_empty() {
+            return DataTypes::null();
+        }
+
+        DataTypes::FloatType(FloatType{value : select_quantile(&mut values, q, interpolation)})
+    }
+}
+
+impl CalculateQuantile for StringType {
+    fn quantile(_ : &[DataTypes], _ : f64, _ : QuantileInterpolation) -> DataTypes {
+        DataTypes::StringType(StringType{value : String::from("NAN")})
+    }
+}
+
+impl CalculateQuantile for NullType {
+    fn quantile(_ : &[DataTypes], _ : f64, _ : QuantileInterpolation) -> DataTypes {
+        DataTypes::null()
+    }
+}
+
+// every most frequent non-null value, in sorted order
+//
+trait CalculateMode {
+    fn mode(column : &[DataTypes]) -> Vec<DataTypes>;
+}
+
+fn most_frequent(columns : &[DataTypes]) -> Vec<DataTypes> {
+    let mut tally : HashMap<&DataTypes, usize> = HashMap::new();
+    for v in columns.iter().filter(|v| !v.is_null()) {
+        *tally.entry(v).or_insert(0) += 1;
+    }
+
+    let top = tally.values().cloned().max().unwrap_or(0);
+    let mut modes : Vec<DataTypes> = tally.into_iter().filter(|(_, c)| *c == top).map(|(v, _)| v.clone()).collect();
+    modes.sort();
+    modes
+}
+
+impl CalculateMode for FloatType {
+    fn mode(columns : &[DataTypes]) -> Vec<DataTypes> {
+        most_frequent(columns)
+    }
+}
+
+impl CalculateMode for IntegerType {
+    fn mode(columns : &[DataTypes]) -> Vec<DataTypes> {
+        most_frequent(columns)
+    }
+}
+
+impl CalculateMode for StringType {
+    fn mode(columns : &[DataTypes]) -> Vec<DataTypes> {
+        most_frequent(columns)
+    }
+}
+
+impl CalculateMode for NullType {
+    fn mode(_ : &[DataTypes]) -> Vec<DataTypes> {
+        Vec::new()
+    }
+}
+
+// higher moments
+//
+// central moments accumulated in a single pass with the online
+// update from Terriberry's extension of Welford's algorithm
+// https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Higher-order_statistics
+//
+#[derive(Copy, Clone, Default)]
+struct Moments {
+    n : usize,
+    mean : f64,
+    m2 : f64,
+    m3 : f64,
+    m4 : f64,
+}
+
+impl Moments {
+
+    // values are shifted by their compensated mean before being
+    // pushed, which keeps the accumulated deviations small
+    //
+    fn new(columns : &[DataTypes]) -> Moments {
+        let values : Vec<f64> = numeric_cells(columns).collect();
+        let shift = if values.is_empty() { 0.0 } else { compensated_sum(values.iter().cloned()) / values.len() as f64 };
+
+        let mut moments = Moments::default();
+        for v in values.iter() {
+            moments.push(v - shift);
+        }
+        moments.mean += shift;
+        moments
+    }
+
+    fn push(&mut self, x : f64) {
+        let n1 = self.n as f64;
+        self.n += 1;
+        let n = self.n as f64;
+
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term1 = delta * delta_n * n1;
+
+        self.mean += delta_n;
+        self.m4 += term1 * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2 - 4.0 * delta_n * self.m3;
+        self.m3 += term1 * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term1;
+    }
+
+    fn skewness(&self, unbiased : bool) -> f64 {
+        let n = self.n as f64;
+        if self.n < if unbiased { 3 } else { 1 } || self.m2 == 0.0 {
+            return f64::NAN;
+        }
+
+        let g1 = n.sqrt() * self.m3 / self.m2.powf(1.5);
+        if unbiased { g1 * (n * (n - 1.0)).sqrt() / (n - 2.0) } else { g1 }
+    }
+
+    fn kurtosis(&self, unbiased : bool) -> f64 {
+        let n = self.n as f64;
+        if self.n < if unbiased { 4 } else { 1 } || self.m2 == 0.0 {
+            return f64::NAN;
+        }
+
+        let g2 = n * self.m4 / (self.m2 * self.m2) - 3.0;
+        if unbiased { ((n + 1.0) * g2 + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0)) } else { g2 }
+    }
+
+    fn variance(&self, ddof : usize) -> f64 {
+        if self.n <= ddof { f64::NAN } else { self.m2 / (self.n - ddof) as f64 }
+    }
+}
+
+// sample skewness, the adjusted Fisher-Pearson coefficient when
+// `unbiased` and the plain moment ratio otherwise
+//
+trait CalculateSkewness {
+    fn skewness(column : &[DataTypes], unbiased : bool) -> DataTypes;
+}
+
+impl CalculateSkewness for FloatType {
+    fn skewness(columns : &[DataTypes], unbiased : bool) -> DataTypes {
+        DataTypes::FloatType(FloatType{value : Moments::new(columns).skewness(unbiased)})
+    }
+}
+
+impl CalculateSkewness for IntegerType {
+    fn skewness(columns : &[DataTypes], unbiased : bool) -> DataTypes {
+        DataTypes::FloatType(FloatType{value : Moments::new(columns).skewness(unbiased)})
+    }
+}
+
+impl CalculateSkewness for StringType {
+    fn skewness(_ : &[DataTypes], _ : bool) -> DataTypes {
+        DataTypes::StringType(StringType{value : String::from("NAN")})
+    }
+}
+
+impl CalculateSkewness for NullType {
+    fn skewness(_ : &[DataTypes], _ : bool) -> DataTypes {
+        DataTypes::null()
+    }
+}
+
+// excess kurtosis (0 for a normal distribution)
+//
+trait CalculateKurtosis {
+    fn kurtosis(column : &[DataTypes], unbiased : bool) -> DataTypes;
+}
+
+impl CalculateKurtosis for FloatType {
+    fn kurtosis(columns : &[DataTypes], unbiased : bool) -> DataTypes {
+        DataTypes::FloatType(FloatType{value : Moments::new(columns).kurtosis(unbiased)})
+    }
+}
+
+impl CalculateKurtosis for IntegerType {
+    fn kurtosis(columns : &[DataTypes], unbiased : bool) -> DataTypes {
+        DataTypes::FloatType(FloatType{value : Moments::new(columns).kurtosis(unbiased)})
+    }
+}
+
+impl CalculateKurtosis for StringType {
+    fn kurtosis(_ : &[DataTypes], _ : bool) -> DataTypes {
+        DataTypes::StringType(StringType{value : String::from("NAN")})
+    }
+}
+
+impl CalculateKurtosis for NullType {
+    fn kurtosis(_ : &[DataTypes], _ : bool) -> DataTypes {
+        DataTypes::null()
+    }
+}
+
+// standard error of the mean, sample standard deviation (n-1) / sqrt(n)
+//
+trait CalculateSem {
+    fn sem(column : &[DataTypes]) -> DataTypes;
+}
+
+impl CalculateSem for FloatType {
+    fn sem(columns : &[DataTypes]) -> DataTypes {
+        let m = Moments::new(columns);
+        DataTypes::FloatType(FloatType{value : m.variance(1).sqrt() / (m.n as f64).sqrt()})
+    }
+}
+
+impl CalculateSem for IntegerType {
+    fn sem(columns : &[DataTypes]) -> DataTypes {
+        let m = Moments::new(columns);
+        DataTypes::FloatType(FloatType{value : m.variance(1).sqrt() / (m.n as f64).sqrt()})
+    }
+}
+
+impl CalculateSem for StringType {
+    fn sem(_ : &[DataTypes]) -> DataTypes {
+        DataTypes::StringType(StringType{value : String::from("NAN")})
+    }
+}
+
+impl CalculateSem for NullType {
+    fn sem(_ : &[DataTypes]) -> DataTypes {
+        DataTypes::null()
+    }
+}
+
+// coefficient of variation, population standard deviation / mean
+//
+trait CalculateCv {
+    fn cv(column : &[DataTypes]) -> DataTypes;
+}
+
+impl CalculateCv for FloatType {
+    fn cv(columns : &[DataTypes]) -> DataTypes {
+        let m = Moments::new(columns);
+        DataTypes::FloatType(FloatType{value : m.variance(0).sqrt() / m.mean})
+    }
+}
+
+impl CalculateCv for IntegerType {
+    fn cv(columns : &[DataTypes]) -> DataTypes {
+        let m = Moments::new(columns);
+        DataTypes::FloatType(FloatType{value : m.variance(0).sqrt() / m.mean})
+    }
+}
+
+impl CalculateCv for StringType {
+    fn cv(_ : &[DataTypes]) -> DataTypes {
+        DataTypes::StringType(StringType{value : String::from("NAN")})
+    }
+}
+
+impl CalculateCv for NullType {
+    fn cv(_ : &[DataTypes]) -> DataTypes {
+        DataTypes::null()
+    }
+}
+
+// correlation
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CorrelationMethod {
+    Pearson,
+    Spearman,
+    Kendall,
+}
+
+// values of the rows where both series hold a finite number; nulls,
+// infinities and strings are dropped
+//
+fn complete_pairs(x : &[DataTypes], y : &[DataTypes]) -> (Vec<f64>, Vec<f64>) {
+    x.iter().zip(y.iter()).map(|(a, b)| (a.fvalue(), b.fvalue())).filter(|(a, b)| a.is_finite() && b.is_finite()).unzip()
+}
+
+// sample covariance (n-1), two pass
+//
+fn covariance(x : &[f64], y : &[f64]) -> f64 {
+    let n = x.len();
+    if n < 2 {
+        return f64::NAN;
+    }
+
+    let xmean = compensated_sum(x.iter().cloned()) / n as f64;
+    let ymean = compensated_sum(y.iter().cloned()) / n as f64;
+    compensated_sum(x.iter().zip(y.iter()).map(|(a, b)| (a - xmean) * (b - ymean))) / (n - 1) as f64
+}
+
+fn pearson(x : &[f64], y : &[f64]) -> f64 {
+    let n = x.len();
+    if n < 2 {
+        return f64::NAN;
+    }
+
+    let xmean = compensated_sum(x.iter().cloned()) / n as f64;
+    let ymean = compensated_sum(y.iter().cloned()) / n as f64;
+    let sxy = compensated_sum(x.iter().zip(y.iter()).map(|(a, b)| (a - xmean) * (b - ymean)));
+    let sxx = compensated_sum(x.iter().map(|a| (a - xmean) * (a - xmean)));
+    let syy = compensated_sum(y.iter().map(|b| (b - ymean) * (b - ymean)));
+
+    sxy / (sxx * syy).sqrt()
+}
+
+// how `rank` scores a run of tied values
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RankMethod {
+    Average,
+    Min,
+    Max,
+    First,
+    Dense,
+}
+
+// where `rank` puts nulls: left null, or ranked before/after every value
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NullRank {
+    Keep,
+    Top,
+    Bottom,
+}
+
+// 1-based ranks of the positions in `order`, which is sorted so that
+// tied positions are adjacent; ranks are indexed by position
+//
+fn ranks_of(order : &[usize], len : usize, method : RankMethod, tied : &dyn Fn(usize, usize) -> bool) -> Vec<f64> {
+    let mut ranks = vec![0.0; len];
+    let mut start = 0;
+    let mut dense = 0.0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && tied(order[start], order[end]) {
+            end += 1;
+        }
+
+        dense += 1.0;
+        for (k, &i) in order[start..end].iter().enumerate() {
+            ranks[i] = match method {
+                RankMethod::Average => (start + end + 1) as f64 / 2.0,
+                RankMethod::Min => (start + 1) as f64,
+                RankMethod::Max => end as f64,
+                RankMethod::First => (start + k + 1) as f64,
+                RankMethod::Dense => dense,
+            };
+        }
+        start = end;
+    }
+
+    ranks
+}
+
+// 1-based ranks, ties share the average of the ranks they span
+//
+fn average_ranks(values : &[f64]) -> Vec<f64> {
+    let mut order : Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&l, &r| values[l].partial_cmp(&values[r]).unwrap());
+
+    ranks_of(&order, values.len(), RankMethod::Average, &|l, r| values[l] == values[r])
+}
+
+// ranks of a series as floats; with `pct` the ranks are divided by the
+// number of ranked values (the highest rank for `RankMethod::Dense`)
+//
+fn rank_series(series : &[DataTypes], method : RankMethod, ascending : bool, pct : bool, nulls : NullRank) -> Series {
+    let mut order : Vec<usize> = (0..series.len()).filter(|&i| nulls != NullRank::Keep || !series[i].is_null()).collect();
+
+    order.sort_by(|&l, &r| match (series[l].is_null(), series[r].is_null()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => if nulls == NullRank::Top { Ordering::Less } else { Ordering::Greater },
+        (false, true) => if nulls == NullRank::Top { Ordering::Greater } else { Ordering::Less },
+        (false, false) => if ascending { series[l].cmp(&series[r]) } else { series[r].cmp(&series[l]) },
+    });
+
+    let ranks = ranks_of(&order, series.len(), method, &|l, r|
+        if series[l].is_null() || series[r].is_null() { series[l].is_null() && series[r].is_null() } else { series[l] == series[r] }
+    );
+
+    let scale = if method == RankMethod::Dense {
+        order.iter().map(|&i| ranks[i]).fold(0.0, f64::max)
+    } else {
+        order.len() as f64
+    };
+
+    (0..series.len()).map(|i| {
+        if nulls == NullRank::Keep && series[i].is_null() {
+            DataTypes::null()
+        } else {
+            DataTypes::FloatType(FloatType{value : if pct { ranks[i] / scale } else { ranks[i] }})
+        }
+    }).collect()
+}
+
+// number of tied pairs, summed over runs of equal adjacent values
+//
+fn tied_pairs<T : PartialEq>(sorted : &[T]) -> f64 {
+    let mut ties = 0.0;
+    let mut run = 1.0;
+    for w in sorted.windows(2) {
+        if w[0] == w[1] {
+            run += 1.0;
+        } else {
+            ties += run * (run - 1.0) / 2.0;
+            run = 1.0;
+        }
+    }
+    ties + run * (run - 1.0) / 2.0
+}
+
+// merge sort that returns the number of inversions it removed
+//
+fn count_swaps(values : &mut Vec<f64>) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+
+    let mut right = values.split_off(values.len() / 2);
+    let mut swaps = count_swaps(values) + count_swaps(&mut right);
+
+    let mut merged = Vec::with_capacity(values.len() + right.len());
+    let (mut i, mut j) = (0, 0);
+    while i < values.len() && j < right.len() {
+        if right[j] < values[i] {
+            swaps += (values.len() - i) as f64;
+            merged.push(right[j]);
+            j += 1;
+        } else {
+            merged.push(values[i]);
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&values[i..]);
+    merged.extend_from_slice(&right[j..]);
+    *values = merged;
+
+    swaps
+}
+
+// Kendall's tau-b in O(n log n), Knight's algorithm
+//
+fn kendall_tau(x : &[f64], y : &[f64]) -> f64 {
+    let n = x.len();
+    if n < 2 {
+        return f64::NAN;
+    }
+
+    let mut pairs : Vec<(f64, f64)> = x.iter().cloned().zip(y.iter().cloned()).collect();
+    pairs.sort_by(|l, r| l.partial_cmp(r).unwrap());
+
+    let xs : Vec<f64> = pairs.iter().map(|p| p.0).collect();
+    let n0 = (n * (n - 1)) as f64 / 2.0;
+    let n1 = tied_pairs(&xs);
+    let n3 = tied_pairs(&pairs);
+
+    let mut ys : Vec<f64> = pairs.iter().map(|p| p.1).collect();
+    let swaps = count_swaps(&mut ys);
+    let n2 = tied_pairs(&ys);
+
+    (n0 - n1 - n2 + n3 - 2.0 * swaps) / ((n0 - n1) * (n0 - n2)).sqrt()
+}
+
+fn correlation(x : &[f64], y : &[f64], method : CorrelationMethod) -> f64 {
+    match method {
+        CorrelationMethod::Pearson => { pearson(x, y) },
+        CorrelationMethod::Spearman => { pearson(&average_ranks(x), &average_ranks(y)) },
+        CorrelationMethod::Kendall => { kendall_tau(x, y) },
+    }
+}
+
+// ordinary least squares fit; `standard_errors` line up with
+// `coefficients` and `residuals` with the rows of the frame, null
+// where a row was left out of the fit
+//
+#[derive(Clone, PartialEq)]
+pub struct Regression {
+    pub intercept : f64,
+    pub coefficients : Vec<f64>,
+    pub intercept_error : f64,
+    pub standard_errors : Vec<f64>,
+    pub r_squared : f64,
+    pub residuals : Vec<DataTypes>,
+}
+
+// inverse of a square matrix by Gauss-Jordan elimination with partial
+// pivoting, None when the matrix is singular
+//
+fn invert(matrix : &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let p = matrix.len();
+    let scale = matrix.iter().flat_map(|r| r.iter()).fold(0.0, |m : f64, v| m.max(v.abs()));
+    let mut a : Vec<Vec<f64>> = matrix.to_vec();
+    let mut inverse : Vec<Vec<f64>> = (0..p).map(|i| (0..p).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+
+    for col in 0..p {
+        let pivot = (col..p).max_by(|&l, &r| a[l][col].abs().partial_cmp(&a[r][col].abs()).unwrap())?;
+        if a[pivot][col].is_nan() || a[pivot][col].abs() <= scale * p as f64 * f64::EPSILON {
+            return None;
+        }
+        a.swap(col, pivot);
+        inverse.swap(col, pivot);
+
+        let d = a[col][col];
+        for j in 0..p {
+            a[col][j] /= d;
+            inverse[col][j] /= d;
+        }
+
+        for row in 0..p {
+            if row != col && a[row][col] != 0.0 {
+                let f = a[row][col];
+                for j in 0..p {
+                    a[row][j] -= f * a[col][j];
+                    inverse[row][j] -= f * inverse[col][j];
+                }
+            }
+        }
+    }
+
+    Some(inverse)
+}
+
+// least squares of `y` on the regressors `xs` (one vector per
+// regressor) with an intercept; the regressors are centered before
+// the normal equations are solved. returns the fit and its residuals
+//
+fn least_squares(y : &[f64], xs : &[Vec<f64>]) -> Result<(Regression, Vec<f64>), DataFrameError> {
+    let n = y.len();
+    let p = xs.len();
+    if n < p + 1 {
+        return Err(DataFrameError::InvalidArgument(format!("{} complete rows can not fit {} coefficients and an intercept", n, p)));
+    }
+
+    let ymean = compensated_sum(y.iter().cloned()) / n as f64;
+    let xmeans : Vec<f64> = xs.iter().map(|x| compensated_sum(x.iter().cloned()) / n as f64).collect();
+
+    let xtx : Vec<Vec<f64>> = (0..p).map(|i| (0..p).map(|j|
+        compensated_sum((0..n).map(|r| (xs[i][r] - xmeans[i]) * (xs[j][r] - xmeans[j])))
+    ).collect()).collect();
+    let xty : Vec<f64> = (0..p).map(|i| compensated_sum((0..n).map(|r| (xs[i][r] - xmeans[i]) * (y[r] - ymean)))).collect();
+
+    let inverse = invert(&xtx).ok_or_else(|| DataFrameError::InvalidArgument(String::from("regressors are collinear or constant")))?;
+
+    let coefficients : Vec<f64> = inverse.iter().map(|row| row.iter().zip(xty.iter()).map(|(a, b)| a * b).sum()).collect();
+    let intercept = ymean - coefficients.iter().zip(xmeans.iter()).map(|(b, m)| b * m).sum::<f64>();
+
+    let residuals : Vec<f64> = (0..n).map(|r|
+        y[r] - intercept - coefficients.iter().zip(xs.iter()).map(|(b, x)| b * x[r]).sum::<f64>()
+    ).collect();
+
+    let ssr = compensated_sum(residuals.iter().map(|e| e * e));
+    let sst = compensated_sum(y.iter().map(|v| (v - ymean) * (v - ymean)));
+    let sigma2 = if n > p + 1 { ssr / (n - p - 1) as f64 } else { f64::NAN };
+
+    let standard_errors : Vec<f64> = (0..p).map(|i| (sigma2 * inverse[i][i]).sqrt()).collect();
+    let spread : f64 = (0..p).map(|i| (0..p).map(|j| xmeans[i] * inverse[i][j] * xmeans[j]).sum::<f64>()).sum();
+    let intercept_error = (sigma2 * (1.0 / n as f64 + spread)).sqrt();
+
+    Ok((Regression{
+        intercept,
+        coefficients,
+        intercept_error,
+        standard_errors,
+        r_squared : if sst > 0.0 { 1.0 - ssr / sst } else { f64::NAN },
+        residuals : Vec::new(),
+    }, residuals))
+}
+
+// Theil-Sen estimator: the median of the slopes between every pair of
+// points with distinct x, and the median of y - slope * x. every slope
+// is held at once, so `theil_sen` caps the number of points at
+// THEIL_SEN_MAX_POINTS (about 12.5 million slopes, 100MB)
+//
+const THEIL_SEN_MAX_POINTS : usize = 5000;
+
+fn theil_sen(x : &[f64], y : &[f64]) -> (f64, f64) {
+    let mut slopes : Vec<f64> = Vec::new();
+    for i in 0..x.len() {
+        for j in i + 1..x.len() {
+            if x[i] != x[j] {
+                slopes.push((y[j] - y[i]) / (x[j] - x[i]));
+            }
+        }
+    }
+
+    if slopes.is_empty() {
+        return (f64::NAN, f64::NAN);
+    }
+
+    let slope = select_quantile(&mut slopes, 0.5, QuantileInterpolation::Linear);
+    let mut offsets : Vec<f64> = x.iter().zip(y.iter()).map(|(a, b)| b - slope * a).collect();
+    (slope, select_quantile(&mut offsets, 0.5, QuantileInterpolation::Linear))
+}
+
+// cross-correlation of x and y at `lag`, sum (x[t] - mx)(y[t+lag] - my)
+// over n * sx * sy; the autocorrelation when x and y are the same
+//
+fn lagged_correlation(x : &[f64], y : &[f64], lag : isize) -> f64 {
+    let n = x.len();
+    let xmean = compensated_sum(x.iter().cloned()) / n as f64;
+    let ymean = compensated_sum(y.iter().cloned()) / n as f64;
+    let sx = compensated_sum(x.iter().map(|v| (v - xmean) * (v - xmean)));
+    let sy = compensated_sum(y.iter().map(|v| (v - ymean) * (v - ymean)));
+
+    let shift = lag.unsigned_abs();
+    let products = (0..n.saturating_sub(shift)).map(|t| {
+        let (i, j) = if lag >= 0 { (t, t + shift) } else { (t + shift, t) };
+        (x[i] - xmean) * (y[j] - ymean)
+    });
+
+    compensated_sum(products) / (sx * sy).sqrt()
+}
+
+// partial autocorrelations 1..=nlags from the autocorrelations
+// `acf` (acf[0] == 1) by the Durbin-Levinson recursion
+//
+fn durbin_levinson(acf : &[f64], nlags : usize) -> Vec<f64> {
+    let mut pacf = Vec::with_capacity(nlags);
+    let mut phi : Vec<f64> = Vec::new();
+
+    for k in 1..=nlags {
+        let num = acf[k] - (1..k).map(|j| phi[j - 1] * acf[k - j]).sum::<f64>();
+        let den = 1.0 - (1..k).map(|j| phi[j - 1] * acf[j]).sum::<f64>();
+        let phi_kk = num / den;
+
+        let mut next : Vec<f64> = (1..k).map(|j| phi[j - 1] - phi_kk * phi[k - j - 1]).collect();
+        next.push(phi_kk);
+        phi = next;
+        pacf.push(phi_kk);
+    }
+
+    pacf
+}
+
+// frame of lag, value and the value's confidence bounds
+//
+fn correlogram(label : &str, lags : Vec<i64>, values : Vec<f64>, errors : Vec<f64>, z : f64) -> DataFrame {
+    let float = |v : f64| DataTypes::FloatType(FloatType{value : v});
+    DataFrame{
+        labels : vec![String::from("lag"), label.to_string(), String::from("lower"), String::from("upper")],
+        columns : vec![
+            lags.iter().map(|&l| DataTypes::IntegerType(IntegerType{value : l})).collect(),
+            values.iter().map(|&v| float(v)).collect(),
+            values.iter().zip(errors.iter()).map(|(v, e)| float(v - z * e)).collect(),
+            values.iter().zip(errors.iter()).map(|(v, e)| float(v + z * e)).collect(),
+        ],
+    }
+}
+
+// whether a seasonal decomposition adds its components or multiplies them
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DecompositionModel {
+    Additive,
+    Multiplicative,
+}
+
+// classical moving average decomposition, or an STL-style one built
+// from loess smoothers (Cleveland et al. 1990, without the robustness
+// iterations)
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DecompositionMethod {
+    Classical,
+    Loess,
+}
+
+// centered moving average over one period, a 2 x period average when
+// the period is even; NaN where the window does not fit
+//
+fn centered_trend(x : &[f64], period : usize) -> Vec<f64> {
+    let half = period / 2;
+    let mut trend = vec![f64::NAN; x.len()];
+
+    for t in half..x.len().saturating_sub(half) {
+        trend[t] = if period % 2 == 1 {
+            compensated_sum(x[t - half..=t + half].iter().cloned()) / period as f64
+        } else {
+            let inner = compensated_sum(x[t - half + 1..t + half].iter().cloned());
+            (inner + 0.5 * (x[t - half] + x[t + half])) / period as f64
+        };
+    }
+
+    trend
+}
+
+fn classical_decomposition(x : &[f64], period : usize, model : DecompositionModel) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let trend = centered_trend(x, period);
+    let detrended : Vec<f64> = x.iter().zip(trend.iter()).map(|(v, t)| match model {
+        DecompositionModel::Additive => v - t,
+        DecompositionModel::Multiplicative => v / t,
+    }).collect();
+
+    let mut means : Vec<f64> = (0..period).map(|k| {
+        let values : Vec<f64> = detrended.iter().skip(k).step_by(period).cloned().filter(|v| !v.is_nan()).collect();
+        compensated_sum(values.iter().cloned()) / values.len() as f64
+    }).collect();
+
+    let level = compensated_sum(means.iter().cloned()) / period as f64;
+    for m in means.iter_mut() {
+        match model {
+            DecompositionModel::Additive => *m -= level,
+            DecompositionModel::Multiplicative => *m /= level,
+        }
+    }
+
+    let seasonal : Vec<f64> = (0..x.len()).map(|t| means[t % period]).collect();
+    let residual : Vec<f64> = (0..x.len()).map(|t| match model {
+        DecompositionModel::Additive => x[t] - trend[t] - seasonal[t],
+        DecompositionModel::Multiplicative => x[t] / (trend[t] * seasonal[t]),
+    }).collect();
+
+    (trend, seasonal, residual)
+}
+
+// local linear loess of `y` (observed at 0, 1, ..) evaluated at `at`,
+// with tricube weights over the `q` nearest observations
+//
+fn loess_at(y : &[f64], q : usize, at : f64) -> f64 {
+    let n = y.len();
+    let width = q.min(n);
+    let lo = (at - (width as f64 - 1.0) / 2.0).round().clamp(0.0, (n - width) as f64) as usize;
+    let hi = lo + width - 1;
+
+    let mut h = (at - lo as f64).max(hi as f64 - at);
+    if q > n {
+        h += ((q - n) / 2) as f64;
+    }
+    let h = h.max(0.5);
+
+    let weights : Vec<f64> = (lo..=hi).map(|i| {
+        let d = (i as f64 - at).abs() / h;
+        if d < 1.0 { (1.0 - d * d * d).powi(3) } else { 0.0 }
+    }).collect();
+
+    let sw : f64 = weights.iter().sum();
+    if sw <= 0.0 {
+        return f64::NAN;
+    }
+    let xmean = (lo..=hi).zip(weights.iter()).map(|(i, w)| w * i as f64).sum::<f64>() / sw;
+    let ymean = (lo..=hi).zip(weights.iter()).map(|(i, w)| w * y[i]).sum::<f64>() / sw;
+    let sxx : f64 = (lo..=hi).zip(weights.iter()).map(|(i, w)| w * (i as f64 - xmean).powi(2)).sum();
+    let sxy : f64 = (lo..=hi).zip(weights.iter()).map(|(i, w)| w * (i as f64 - xmean) * (y[i] - ymean)).sum();
+
+    if sxx <= f64::EPSILON * sw { ymean } else { ymean + sxy / sxx * (at - xmean) }
+}
+
+fn loess(y : &[f64], q : usize) -> Vec<f64> {
+    (0..y.len()).map(|i| loess_at(y, q, i as f64)).collect()
+}
+
+fn moving_average(x : &[f64], window : usize) -> Vec<f64> {
+    x.windows(window).map(|w| compensated_sum(w.iter().cloned()) / window as f64).collect()
+}
+
+#[allow(clippy::manual_is_multiple_of)]
+fn next_odd(x : f64) -> usize {
+    let n = x.ceil() as usize;
+    if n % 2 == 0 { n + 1 } else { n }
+}
+
+// STL inner loop: cycle-subseries smoothing, low-pass filtering of the
+// seasonal and loess smoothing of the trend, repeated twice
+//
+fn stl_decomposition(x : &[f64], period : usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    const SEASONAL_SPAN : usize = 7;
+    let n = x.len();
+    let low_pass_span = next_odd(period as f64);
+    let trend_span = next_odd(1.5 * period as f64 / (1.0 - 1.5 / SEASONAL_SPAN as f64));
+
+    let mut trend = vec![0.0; n];
+    let mut seasonal = vec![0.0; n];
+
+    for _ in 0..2 {
+        let detrended : Vec<f64> = x.iter().zip(trend.iter()).map(|(v, t)| v - t).collect();
+
+        // each cycle-subseries smoothed and extended by one period on both ends
+        let mut cycle = vec![0.0; n + 2 * period];
+        for k in 0..period {
+            let sub : Vec<f64> = detrended.iter().skip(k).step_by(period).cloned().collect();
+            for j in 0..sub.len() + 2 {
+                cycle[k + j * period] = loess_at(&sub, SEASONAL_SPAN, j as f64 - 1.0);
+            }
+        }
+
+        let low_pass = loess(&moving_average(&moving_average(&moving_average(&cycle, period), period), 3), low_pass_span);
+        seasonal = (0..n).map(|t| cycle[t + period] - low_pass[t]).collect();
+
+        let deseasonalized : Vec<f64> = x.iter().zip(seasonal.iter()).map(|(v, s)| v - s).collect();
+        trend = loess(&deseasonalized, trend_span);
+    }
+
+    let residual = (0..n).map(|t| x[t] - trend[t] - seasonal[t]).collect();
+    (trend, seasonal, residual)
+}
+
+// how `interpolate` fills a gap between two values: along the row
+// positions, along a datetime index, with the nearest value, with a
+// polynomial through the `order` + 1 nearest values or a natural
+// cubic spline through every value
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InterpolationMethod {
+    Linear,
+    Time,
+    Nearest,
+    Polynomial(usize),
+    CubicSpline,
+}
+
+// which nulls of a run `interpolate` may fill when given a limit,
+// counted from the value before the run, the value after it, or both;
+// also whether leading (Backward) and trailing (Forward) runs are filled
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LimitDirection {
+    Forward,
+    Backward,
+    Both,
+}
+
+// second derivatives of the natural cubic spline through (x, y)
+//
+fn spline_derivatives(x : &[f64], y : &[f64]) -> Vec<f64> {
+    let n = x.len();
+    let mut m = vec![0.0; n];
+    if n < 3 {
+        return m;
+    }
+
+    // Thomas algorithm on the tridiagonal system of the interior points
+    let mut diag = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    for i in 1..n - 1 {
+        let (h0, h1) = (x[i] - x[i - 1], x[i + 1] - x[i]);
+        diag[i] = 2.0 * (h0 + h1);
+        rhs[i] = 6.0 * ((y[i + 1] - y[i]) / h1 - (y[i] - y[i - 1]) / h0);
+        if i > 1 {
+            let w = h0 / diag[i - 1];
+            diag[i] -= w * h0;
+            rhs[i] -= w * rhs[i - 1];
+        }
+    }
+
+    for i in (1..n - 1).rev() {
+        m[i] = (rhs[i] - (x[i + 1] - x[i]) * m[i + 1]) / diag[i];
+    }
+    m
+}
+
+fn lagrange(xs : &[f64], ys : &[f64], at : f64) -> f64 {
+    (0..xs.len()).map(|i| {
+        ys[i] * (0..xs.len()).filter(|&j| j != i).map(|j| (at - xs[j]) / (xs[i] - xs[j])).product::<f64>()
+    }).sum()
+}
+
+// fills the nulls of `y` observed at `x`; runs before the first and
+// after the last value repeat that value
+//
+fn interpolate_series(x : &[f64], y : &[DataTypes], method : InterpolationMethod, limit : Option<usize>, direction : LimitDirection) -> Series {
+    let valid : Vec<usize> = (0..y.len()).filter(|&i| !y[i].is_null()).collect();
+    let float = |v : f64| DataTypes::FloatType(FloatType{value : v});
+    let keep = |v : &DataTypes| if method == InterpolationMethod::Nearest || v.is_null() { v.clone() } else { float(v.fvalue()) };
+
+    let mut result : Series = y.iter().map(keep).collect();
+    if valid.is_empty() {
+        return result;
+    }
+
+    let vx : Vec<f64> = valid.iter().map(|&i| x[i]).collect();
+    let vy : Vec<f64> = valid.iter().map(|&i| y[i].fvalue()).collect();
+    let derivatives = if method == InterpolationMethod::CubicSpline { spline_derivatives(&vx, &vy) } else { Vec::new() };
+
+    let mut start = 0;
+    while start < y.len() {
+        if !y[start].is_null() {
+            start += 1;
+            continue;
+        }
+        let end = start + y[start..].iter().take_while(|v| v.is_null()).count();
+
+        // valid[k - 1] precedes the run and valid[k] follows it
+        let k = valid.partition_point(|&i| i < start);
+        let leading = k == 0;
+        let trailing = k == valid.len();
+        let allowed = match direction {
+            LimitDirection::Forward => !leading,
+            LimitDirection::Backward => !trailing,
+            LimitDirection::Both => true,
+        };
+
+        for i in start..end {
+            let within = match (limit, direction) {
+                (None, _) => true,
+                (Some(l), LimitDirection::Forward) => i - start < l,
+                (Some(l), LimitDirection::Backward) => end - 1 - i < l,
+                (Some(l), LimitDirection::Both) => i - start < l || end - 1 - i < l,
+            };
+            if !allowed || !within {
+                continue;
+            }
+
+            result[i] = if leading {
+                keep(&y[valid[0]])
+            } else if trailing {
+                keep(&y[valid[k - 1]])
+            } else {
+                let (lo, hi) = (k - 1, k);
+                let t = (x[i] - vx[lo]) / (vx[hi] - vx[lo]);
+                match method {
+                    InterpolationMethod::Linear | InterpolationMethod::Time => float(vy[lo] + t * (vy[hi] - vy[lo])),
+                    InterpolationMethod::Nearest => y[valid[if t <= 0.5 { lo } else { hi }]].clone(),
+                    InterpolationMethod::Polynomial(order) => {
+                        let count = (order + 1).min(valid.len());
+                        let first = (k as isize - (count as isize + 1) / 2).clamp(0, (valid.len() - count) as isize) as usize;
+                        float(lagrange(&vx[first..first + count], &vy[first..first + count], x[i]))
+                    },
+                    InterpolationMethod::CubicSpline => {
+                        let h = vx[hi] - vx[lo];
+                        let (a, b) = (1.0 - t, t);
+                        float(a * vy[lo] + b * vy[hi] + ((a * a * a - a) * derivatives[lo] + (b * b * b - b) * derivatives[hi]) * h * h / 6.0)
+                    },
+                }
+            };
+        }
+
+        start = end;
+    }
+
+    result
+}
+
+type Series = Vec<DataTypes>;
+
+fn series_type(series : &Series) -> &DataTypes {
+    series.iter().find(|v| !matches!(v, DataTypes::NullType(_))).unwrap_or(&NULL_VALUE)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataFrameError {
+    ColumnNotFound(String),
+    MergeValidation(String),
+    NotSorted(String),
+    InvalidArgument(String),
+}
+
+impl fmt::Display for DataFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataFrameError::ColumnNotFound(name) => write!(f, "column '{}' not found", name),
+            DataFrameError::MergeValidation(msg) => write!(f, "merge validation failed: {}", msg),
+            DataFrameError::NotSorted(name) => write!(f, "column '{}' is not sorted", name),
+            DataFrameError::InvalidArgument(msg) => write!(f, "invalid argument: {}", msg),
+        }
+    }
+}
+
+impl Error for DataFrameError {}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Outer,
+    Semi,
+    Anti,
+    Cross,
+}
+
+// which right row an as-of join picks for a left key: the last
+// one at or before it, the first one at or after it, or the closer
+// of the two (ties go backward)
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AsofDirection {
+    Backward,
+    Forward,
+    Nearest,
+}
+
+// bins for `cut`, `qcut` and `histogram`: either a number of equal
+// width bins (equal frequency for `qcut`) or the explicit bin edges
+// (quantile levels in [0, 1] for `qcut`)
+//
+#[derive(Clone, Debug, PartialEq)]
+pub enum Bins {
+    Count(usize),
+    Edges(Vec<f64>),
+}
+
+// what the counts of a `crosstab` are divided by
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Normalize {
+    Off,
+    All,
+    Rows,
+    Columns,
+}
+
+// reductions used by `pivot_table` and `bootstrap`; nulls are
+// dropped before the reduction is applied, StdDev and Variance
+// carry their ddof
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Aggregation {
+    Count,
+    Sum,
+    Mean,
+    StdDev(usize),
+    Variance(usize),
+    Min,
+    Max,
+    Median,
+    Quantile(f64, QuantileInterpolation),
+}
+
+impl Aggregation {
+
+    fn check(&self) -> Result<(), DataFrameError> {
+        match self {
+            Aggregation::Quantile(q, _) => check_quantile(*q),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn apply(&self, series : &Series) -> DataTypes {
+        let values : Series = series.iter().filter(|v| !v.is_null()).cloned().collect();
+
+        if *self == Aggregation::Count {
+            return DataTypes::IntegerType(IntegerType{value : values.len() as i64});
+        }
+        else if values.is_empty() {
+            return DataTypes::null();
+        }
+
+        match series_type(&values) {
+            DataTypes::FloatType(_) => { self.apply_as::<FloatType>(&values) },
+            DataTypes::IntegerType(_) => { self.apply_as::<IntegerType>(&values) },
+            DataTypes::StringType(_) => { self.apply_as::<StringType>(&values) },
+            DataTypes::NullType(_) => { self.apply_as::<NullType>(&values) },
+        }
+    }
+
+    fn apply_as<T>(&self, values : &Series) -> DataTypes
+        where T : CalculateSum + CalculateMean + CalculateStdDev + CalculateVariance + CalculateMin + CalculateMax + CalculateQuantile
+    {
+        match self {
+            Aggregation::Count => { DataTypes::IntegerType(IntegerType{value : values.len() as i64}) },
+            Aggregation::Sum => { T::sum(values) },
+            Aggregation::Mean => { T::mean(values) },
+            Aggregation::StdDev(ddof) => { T::stddev(values, *ddof) },
+            Aggregation::Variance(ddof) => { T::variance(values, *ddof) },
+            Aggregation::Min => { T::min(values) },
+            Aggregation::Max => { T::max(values) },
+            Aggregation::Median => { T::quantile(values, 0.5, QuantileInterpolation::Linear) },
+            Aggregation::Quantile(q, interpolation) => { T::quantile(values, *q, *interpolation) },
+        }
+    }
+}
+
+// how `bootstrap` turns the resampled statistics into an interval:
+// their plain percentiles, or bias-corrected and accelerated ones
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BootstrapMethod {
+    Percentile,
+    BCa,
+}
+
+// a statistic and its bootstrap confidence interval
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ConfidenceInterval {
+    pub estimate : f64,
+    pub lower : f64,
+    pub upper : f64,
+}
+
+// SplitMix64 (Steele, Lea and Flood), a small seedable generator
+//
+struct SplitMix64 {
+    state : u64,
+}
+
+impl SplitMix64 {
+
+    fn new(seed : u64) -> SplitMix64 {
+        SplitMix64{ state : seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // uniform in [0, n)
+    //
+    fn below(&mut self, n : usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+}
+
+// bootstrap interval of `aggfunc` over the non-null `values`; BCa
+// estimates the acceleration by jackknife, which applies `aggfunc`
+// once per value
+//
+fn bootstrap_interval(values : &Series, aggfunc : Aggregation, method : BootstrapMethod, confidence : f64, resamples : usize, rng : &mut SplitMix64) -> ConfidenceInterval {
+    let estimate = aggfunc.apply(values).fvalue();
+    if values.is_empty() {
+        return ConfidenceInterval{ estimate, lower : f64::NAN, upper : f64::NAN };
+    }
+
+    let n = values.len();
+    let mut stats : Vec<f64> = (0..resamples).map(|_| {
+        let sample : Series = (0..n).map(|_| values[rng.below(n)].clone()).collect();
+        aggfunc.apply(&sample).fvalue()
+    }).filter(|s| !s.is_nan()).collect();
+
+    let alpha = (1.0 - confidence) / 2.0;
+    let (lo, hi) = match method {
+        BootstrapMethod::Percentile => (alpha, 1.0 - alpha),
+        BootstrapMethod::BCa => {
+            let below = stats.iter().filter(|&&s| s < estimate).count() + stats.iter().filter(|&&s| s <= estimate).count();
+            let z0 = hypothesis::normal_ppf(below as f64 / (2 * stats.len()) as f64);
+
+            let jackknife : Vec<f64> = (0..n).map(|i| {
+                let sample : Series = values.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, v)| v.clone()).collect();
+                aggfunc.apply(&sample).fvalue()
+            }).collect();
+            let mean = compensated_sum(jackknife.iter().cloned()) / n as f64;
+            let num : f64 = jackknife.iter().map(|t| (mean - t).powi(3)).sum();
+            let den : f64 = jackknife.iter().map(|t| (mean - t).powi(2)).sum();
+            let a = if den > 0.0 { num / (6.0 * den.powf(1.5)) } else { 0.0 };
+
+            let adjust = |z : f64| 1.0 - hypothesis::normal_sf(z0 + (z0 + z) / (1.0 - a * (z0 + z)));
+            (adjust(hypothesis::normal_ppf(alpha)), adjust(hypothesis::normal_ppf(1.0 - alpha)))
+        },
+    };
+
+    if lo.is_nan() || hi.is_nan() {
+        return ConfidenceInterval{ estimate, lower : f64::NAN, upper : f64::NAN };
+    }
+
+    ConfidenceInterval{
+        estimate,
+        lower : select_quantile(&mut stats, lo, QuantileInterpolation::Linear),
+        upper : select_quantile(&mut stats, hi, QuantileInterpolation::Linear),
+    }
+}
+
+fn check_confidence(confidence : f64) -> Result<(), DataFrameError> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(DataFrameError::InvalidArgument(format!("confidence {} is not in (0, 1)", confidence)));
+    }
+    Ok(())
+}
+
+fn check_bootstrap(confidence : f64, resamples : usize) -> Result<(), DataFrameError> {
+    check_confidence(confidence)?;
+    if resamples == 0 {
+        return Err(DataFrameError::InvalidArgument(String::from("bootstrap needs at least one resample")));
+    }
+    Ok(())
+}
+
+// what `fillna` puts in place of a null: one value for every column,
+// a value per column (other columns are left alone), the last value
+// before the null or the first value after it
+//
+#[derive(Clone)]
+pub enum FillNa {
+    Value(DataTypes),
+    Columns(HashMap<String, DataTypes>),
+    Forward,
+    Backward,
+}
+
+// `dropna` drops a row when any or all of its checked values are null
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DropHow {
+    Any,
+    All,
+}
+
+// which of a set of duplicate rows is not marked as a duplicate
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Keep {
+    First,
+    Last,
+    None,
+}
+
+// the type of a column's values, the target of `cast` and what
+// `dtypes` reports
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ColumnType {
+    Float,
+    Integer,
+    String,
+    Null,
+}
+
+// whether `cast` fails on a value that does not convert or makes it null
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CastMode {
+    Strict,
+    Lenient,
+}
+
+// key uniqueness checked before joining, named left-to-right
+//
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum JoinValidate {
+    ManyToMany,
+    OneToOne,
+    OneToMany,
+    ManyToOne,
+}
+
+pub struct DataFrame {
+    labels : Vec<String>,
+    columns : Vec<Series>,
+}
+
+impl Default for DataFrame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataFrame {
+
+    pub fn new() -> DataFrame {
+        DataFrame{labels : Vec::new() , columns : Vec::new() }
+    }
+
+    // replaces the frame's contents with the csv file at `path`; the
+    // first line is the header. a file that can not be read, a row
+    // with more or fewer fields than the header and a repeated header
+    // label are errors, and the frame is left unchanged on error
+    //
+    pub fn load_csv(&mut self, path : &Path) -> Result<(), DataFrameError> {
+        let contents : String = read_to_string(path).map_err(|e| DataFrameError::InvalidArgument(format!("can not read '{}': {}", path.display(), e)))?;
+        let mut split_contents : std::str::Lines = contents.lines();
+        let header = split_contents.next().ok_or_else(|| DataFrameError::InvalidArgument(format!("'{}' has no header", path.display())))?;
+
+        let fields : Vec<&str> = header.split(',').collect();
+        let mut columns : Vec<Series> = (0..fields.len()).map(|_x| Vec::new() ).collect();
+
+        for (row, ln) in split_contents.enumerate() {
+            let values : Vec<&str> = ln.split(',').collect();
+            if values.len() != fields.len() {
+                return Err(DataFrameError::InvalidArgument(format!("row {} of '{}' has {} fields, the header has {}", row + 1, path.display(), values.len(), fields.len())));
+            }
+
+            for (x, y) in (0..values.len()).zip(values) {
+                if let Ok(fvalue) = y.parse::<f64>() {
+                    columns[x].push(DataTypes::FloatType(FloatType{value : fvalue}));
+                }
+                else if let Ok(ivalue) = y.parse::<i64>() {
+                    columns[x].push(DataTypes::IntegerType(IntegerType{value : ivalue}));
+                }
+                else {
+                    columns[x].push(DataTypes::StringType(StringType{value : String::from(y)}));
+                }
+           }
+        }
+
+        let data : Vec<(&str, Series)> = fields.into_iter().zip(columns).collect();
+        self.load_data(&data, false)
+    }
+
+    // replaces the frame's contents with `data`; every column must
+    // have the same length and, unless `allow_duplicates`, a label of
+    // its own. the frame is left unchanged on error
+    //
+    pub fn load_data(&mut self, data : & Vec<(&str, Series)>, allow_duplicates : bool) -> Result<(), DataFrameError> {
+        if let Some((label, series)) = data.iter().find(|(_, series)| series.len() != data[0].1.len()) {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' has {} rows, column '{}' has {}", label, series.len(), data[0].0, data[0].1.len())));
+        }
+        if !allow_duplicates {
+            if let Some((i, _)) = data.iter().enumerate().find(|(i, (label, _))| data[..*i].iter().any(|(l, _)| l == label)) {
+                return Err(DataFrameError::InvalidArgument(format!("column '{}' appears twice", data[i].0)));
+            }
+        }
+
+        self.labels = data.iter().map(|(label, _)| label.to_string()).collect();
+        self.columns = data.iter().map(|(_, series)| series.clone()).collect();
+
+        Ok(())
+    }
+
+    // stacks `frames` vertically, aligning columns by label in the
+    // order they are first seen; columns missing from a frame are
+    // filled with nulls. `source` names an extra integer column that
+    // records which frame each row came from and must not repeat a
+    // label of the result
+    //
+    pub fn concat_rows(frames : &[&DataFrame], source : Option<&str>) -> Result<DataFrame, DataFrameError> {
+        let mut result = DataFrame::new();
+        for frame in frames {
+            result.append(frame);
+        }
+
+        if let Some(name) = source {
+            if result.labels.iter().any(|l| l == name) {
+                return Err(DataFrameError::InvalidArgument(format!("source column '{}' is already a column", name)));
+            }
+
+            let origin : Series = frames.iter().enumerate().flat_map(|(i, f)|
+                std::iter::repeat_n(DataTypes::IntegerType(IntegerType{value : i as i64}), f.nrows())
+            ).collect();
+            result.labels.push(name.to_string());
+            result.columns.push(origin);
+        }
+
+        Ok(result)
+    }
+
+    // places `frames` side by side, aligning rows by position; shorter
+    // frames are padded with nulls and labels must not repeat
+    //
+    pub fn concat_columns(frames : &[&DataFrame]) -> Result<DataFrame, DataFrameError> {
+        let row_count = frames.iter().map(|f| f.nrows()).max().unwrap_or(0);
+        let mut result = DataFrame::new();
+
+        for frame in frames {
+            for (label, column) in frame.labels.iter().zip(frame.columns.iter()) {
+                if result.labels.contains(label) {
+                    return Err(DataFrameError::InvalidArgument(format!("column '{}' appears in more than one frame", label)));
+                }
+
+                let mut values = column.clone();
+                values.resize(row_count, DataTypes::null());
+                result.labels.push(label.to_string());
+                result.columns.push(values);
+            }
+        }
+
+        Ok(result)
+    }
+
+    // appends the rows of `other` in place, matching columns by label;
+    // columns only one side has are filled with nulls
+    //
+    pub fn append(&mut self, other : &DataFrame) {
+        let row_count = self.nrows();
+        let other_count = other.nrows();
+
+        for (label, column) in other.labels.iter().zip(other.columns.iter()) {
+            match self.labels.iter().position(|l| l == label) {
+                Some(ci) => self.columns[ci].extend_from_slice(column),
+                None => {
+                    let mut values : Series = Vec::with_capacity(row_count + other_count);
+                    values.resize(row_count, DataTypes::null());
+                    values.extend_from_slice(column);
+                    self.labels.push(label.to_string());
+                    self.columns.push(values);
+                },
+            }
+        }
+
+        for column in self.columns.iter_mut() {
+            column.resize(row_count + other_count, DataTypes::null());
+        }
+    }
+
+    // appends a column as long as the others; its label must be new
+    // unless `allow_duplicates`
+    //
+    pub fn add_column(&mut self, data : &(&str, Series), allow_duplicates : bool) -> Result<(), DataFrameError> {
+        if !allow_duplicates && self.labels.iter().any(|l| l == data.0) {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' already exists", data.0)));
+        }
+        if !self.columns.is_empty() && data.1.len() != self.nrows() {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' has {} rows, the frame has {}", data.0, data.1.len(), self.nrows())));
+        }
+
+        self.labels.push(data.0.to_string());
+        self.columns.push(data.1.clone());
+        Ok(())
+    }
+
+    // inserts a column before the one at `position` (at the end when
+    // `position` is the number of columns); the label must be new and
+    // the column as long as the others
+    //
+    pub fn insert_column(&mut self, position : usize, data : &(&str, Series)) -> Result<(), DataFrameError> {
+        if position > self.columns.len() {
+            return Err(DataFrameError::InvalidArgument(format!("position {} is past the {} columns", position, self.columns.len())));
+        }
+        if self.labels.iter().any(|l| l == data.0) {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' already exists", data.0)));
+        }
+        if !self.columns.is_empty() && data.1.len() != self.nrows() {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' has {} rows, the frame has {}", data.0, data.1.len(), self.nrows())));
+        }
+
+        self.labels.insert(position, data.0.to_string());
+        self.columns.insert(position, data.1.clone());
+        Ok(())
+    }
+
+    // the frame without the columns in `column_names`
+    //
+    pub fn drop(&self, column_names : Vec<&str>) -> Result<DataFrame, DataFrameError> {
+        let idxs : Vec<usize> = column_names.iter().map(|&x| self.find_column_index(x)).collect::<Result<_, _>>()?;
+        let kept : Vec<usize> = (0..self.columns.len()).filter(|i| !idxs.contains(i)).collect();
+        Ok(self.take_columns(&kept))
+    }
+
+    // the columns in `column_names`, in that order; a column may be
+    // named once
+    //
+    pub fn select(&self, column_names : Vec<&str>) -> Result<DataFrame, DataFrameError> {
+        let idxs : Vec<usize> = column_names.iter().map(|&x| self.find_column_index(x)).collect::<Result<_, _>>()?;
+        if let Some(dup) = column_names.iter().enumerate().find(|(i, x)| column_names[..*i].contains(x)) {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' is selected twice", dup.1)));
+        }
+        Ok(self.take_columns(&idxs))
+    }
+
+    // every column, in the order of `column_names`
+    //
+    pub fn reorder(&self, column_names : Vec<&str>) -> Result<DataFrame, DataFrameError> {
+        if column_names.len() != self.columns.len() {
+            return Err(DataFrameError::InvalidArgument(format!("{} columns given to reorder {}", column_names.len(), self.columns.len())));
+        }
+        self.select(column_names)
+    }
+
+    pub fn rename(&self, from : &str, to : &str) -> Result<DataFrame, DataFrameError> {
+        let mut mapping = HashMap::new();
+        mapping.insert(from, to);
+        self.rename_columns(&mapping)
+    }
+
+    // relabels the columns named by the keys of `mapping`; the new
+    // labels must not repeat
+    //
+    pub fn rename_columns(&self, mapping : &HashMap<&str, &str>) -> Result<DataFrame, DataFrameError> {
+        for name in mapping.keys() {
+            self.find_column_index(name)?;
+        }
+
+        let labels : Vec<String> = self.labels.iter().map(|l| mapping.get(l.as_str()).map_or(l.to_string(), |n| n.to_string())).collect();
+        if let Some(dup) = labels.iter().enumerate().find(|(i, l)| labels[..*i].contains(l)) {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' would appear twice", dup.1)));
+        }
+
+        Ok(DataFrame{ labels, columns : self.columns.clone() })
+    }
+
+    // the frame with `column_name` converted to `to`; strings are parsed
+    // and floats only convert to integers when they are whole. a value
+    // that does not convert is an error in `CastMode::Strict` and null
+    // in `CastMode::Lenient`. `ColumnType::Null` is not a cast target
+    //
+    pub fn cast(&self, column_name : &str, to : ColumnType, mode : CastMode) -> Result<DataFrame, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+        if to == ColumnType::Null {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' can not be cast to Null", column_name)));
+        }
+        let values : Series = self.columns[idx].iter().enumerate().map(|(r, v)| match (v.cast(to), mode) {
+            (Some(c), _) => Ok(c),
+            (None, CastMode::Lenient) => Ok(DataTypes::null()),
+            (None, CastMode::Strict) => Err(DataFrameError::InvalidArgument(format!("'{}' in row {} of column '{}' is not a valid {:?}", v.svalue(), r, column_name, to))),
+        }).collect::<Result<_, _>>()?;
+
+        let mut result = self.take_columns(&(0..self.columns.len()).collect::<Vec<usize>>());
+        result.columns[idx] = values;
+        Ok(result)
+    }
+
+    fn take_columns(&self, idxs : &[usize]) -> DataFrame {
+        DataFrame{
+            labels : idxs.iter().map(|&i| self.labels[i].to_string()).collect(),
+            columns : idxs.iter().map(|&i| self.columns[i].clone()).collect(),
+        }
+    }
+
+    pub fn get_column_index(&self, column_name : &str) -> usize {
+        self.labels.iter().position(|l| *l == column_name ).unwrap()
+    }
+
+    // (rows, columns)
+    //
+    pub fn shape(&self) -> (usize, usize) {
+        (self.nrows(), self.ncols())
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.columns.first().map_or(0, |c| c.len())
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.columns.len()
+    }
+
+    // label and type of every column, in column order; the type is
+    // that of the first non-null value, Null for all-null columns
+    //
+    pub fn dtypes(&self) -> Vec<(String, ColumnType)> {
+        (0..self.columns.len()).map(|i| (self.labels[i].to_string(), match self.column_type(i) {
+            DataTypes::FloatType(_) => ColumnType::Float,
+            DataTypes::IntegerType(_) => ColumnType::Integer,
+            DataTypes::StringType(_) => ColumnType::String,
+            DataTypes::NullType(_) => ColumnType::Null,
+        })).collect()
+    }
+
+    // the first non-null value decides which typed implementation
+    // a column is dispatched to; all-null columns dispatch to NullType
+    //
+    fn column_type(&self, idx : usize) -> &DataTypes {
+        series_type(&self.columns[idx])
+    }
+
+    pub fn column_is_integer(&self, idx : usize) -> bool {
+        matches!(self.column_type(idx), DataTypes::IntegerType(_))
+    }
+
+    pub fn column_is_float(&self, idx : usize) -> bool {
+        matches!(self.column_type(idx), DataTypes::FloatType(_))
+    }
+
+    pub fn column_is_string(&self, idx : usize) -> bool {
+        matches!(self.column_type(idx), DataTypes::StringType(_))
     }
 
     pub fn column(&self, column_name : &str) -> DataFrame {
         let idx = self.get_column_index(column_name);
 
-        let op = match self.columns[idx].first().unwrap() {
-            DataTypes::FloatType(FloatType{value : _}) => { FloatType::column },
-            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::column },
-            DataTypes::StringType(StringType{value : _}) => { StringType::column },
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::column },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::column },
+            DataTypes::StringType(StringType{value : _}) => { StringType::column },
+            DataTypes::NullType(_) => { NullType::column },
+        };
+
+        op(&self.columns[idx], column_name)
+    }
+
+    pub fn series(&self, column_name : &str) -> Series {
+        let idx = self.get_column_index(column_name);
+        self.columns[idx].clone()
+    }
+
+    pub fn mean(&self, column_name : &str) -> DataTypes {
+        let idx = self.get_column_index(column_name);
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::mean },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::mean },
+            DataTypes::StringType(StringType{value : _}) => { StringType::mean },
+            DataTypes::NullType(_) => { NullType::mean },
+        };
+
+        op(&self.columns[idx])
+    }
+
+    // `ddof` is subtracted from the count of non-null values in the
+    // denominator, 1 for the sample and 0 for the population statistic
+    //
+    pub fn stddev(&self, column_name : &str, ddof : usize) -> DataTypes {
+        let idx = self.get_column_index(column_name);
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::stddev },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::stddev },
+            DataTypes::StringType(StringType{value : _}) => { StringType::stddev },
+            DataTypes::NullType(_) => { NullType::stddev },
+        };
+
+        op(&self.columns[idx], ddof)
+    }
+
+    pub fn pstddev(&self, column_name : &str) -> DataTypes {
+        self.stddev(column_name, 0)
+    }
+
+    pub fn variance(&self, column_name : &str, ddof : usize) -> DataTypes {
+        let idx = self.get_column_index(column_name);
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::variance },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::variance },
+            DataTypes::StringType(StringType{value : _}) => { StringType::variance },
+            DataTypes::NullType(_) => { NullType::variance },
+        };
+
+        op(&self.columns[idx], ddof)
+    }
+
+    pub fn sum(&self, column_name : &str) -> DataTypes {
+        let idx = self.get_column_index(column_name);
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::sum },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::sum },
+            DataTypes::StringType(StringType{value : _}) => { StringType::sum },
+            DataTypes::NullType(_) => { NullType::sum },
+        };
+
+        op(&self.columns[idx])
+    }
+
+    pub fn min(&self, column_name : &str) -> Result<DataTypes, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::min },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::min },
+            DataTypes::StringType(StringType{value : _}) => { StringType::min },
+            DataTypes::NullType(_) => { NullType::min },
+        };
+
+        Ok(op(&self.columns[idx]))
+    }
+
+    pub fn max(&self, column_name : &str) -> Result<DataTypes, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::max },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::max },
+            DataTypes::StringType(StringType{value : _}) => { StringType::max },
+            DataTypes::NullType(_) => { NullType::max },
+        };
+
+        Ok(op(&self.columns[idx]))
+    }
+
+    pub fn argmin(&self, column_name : &str) -> Result<Option<usize>, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::argmin },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::argmin },
+            DataTypes::StringType(StringType{value : _}) => { StringType::argmin },
+            DataTypes::NullType(_) => { NullType::argmin },
+        };
+
+        Ok(op(&self.columns[idx]))
+    }
+
+    pub fn argmax(&self, column_name : &str) -> Result<Option<usize>, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::argmax },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::argmax },
+            DataTypes::StringType(StringType{value : _}) => { StringType::argmax },
+            DataTypes::NullType(_) => { NullType::argmax },
+        };
+
+        Ok(op(&self.columns[idx]))
+    }
+
+    pub fn median(&self, column_name : &str) -> Result<DataTypes, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+        Ok(self.column_quantile(idx, 0.5, QuantileInterpolation::Linear))
+    }
+
+    // `q` outside [0, 1] is an InvalidArgument
+    //
+    pub fn quantile(&self, column_name : &str, q : f64, interpolation : QuantileInterpolation) -> Result<DataTypes, DataFrameError> {
+        check_quantile(q)?;
+        let idx = self.find_column_index(column_name)?;
+        Ok(self.column_quantile(idx, q, interpolation))
+    }
+
+    fn column_quantile(&self, idx : usize, q : f64, interpolation : QuantileInterpolation) -> DataTypes {
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::quantile },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::quantile },
+            DataTypes::StringType(StringType{value : _}) => { StringType::quantile },
+            DataTypes::NullType(_) => { NullType::quantile },
+        };
+
+        op(&self.columns[idx], q, interpolation)
+    }
+
+    pub fn mode(&self, column_name : &str) -> Result<DataFrame, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::mode },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::mode },
+            DataTypes::StringType(StringType{value : _}) => { StringType::mode },
+            DataTypes::NullType(_) => { NullType::mode },
+        };
+
+        Ok(DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![op(&self.columns[idx]),] })
+    }
+
+    pub fn skewness(&self, column_name : &str, unbiased : bool) -> Result<DataTypes, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::skewness },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::skewness },
+            DataTypes::StringType(StringType{value : _}) => { StringType::skewness },
+            DataTypes::NullType(_) => { NullType::skewness },
+        };
+
+        Ok(op(&self.columns[idx], unbiased))
+    }
+
+    pub fn kurtosis(&self, column_name : &str, unbiased : bool) -> Result<DataTypes, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::kurtosis },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::kurtosis },
+            DataTypes::StringType(StringType{value : _}) => { StringType::kurtosis },
+            DataTypes::NullType(_) => { NullType::kurtosis },
+        };
+
+        Ok(op(&self.columns[idx], unbiased))
+    }
+
+    pub fn sem(&self, column_name : &str) -> Result<DataTypes, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::sem },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::sem },
+            DataTypes::StringType(StringType{value : _}) => { StringType::sem },
+            DataTypes::NullType(_) => { NullType::sem },
+        };
+
+        Ok(op(&self.columns[idx]))
+    }
+
+    pub fn cv(&self, column_name : &str) -> Result<DataTypes, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::cv },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::cv },
+            DataTypes::StringType(StringType{value : _}) => { StringType::cv },
+            DataTypes::NullType(_) => { NullType::cv },
+        };
+
+        Ok(op(&self.columns[idx]))
+    }
+
+    pub fn simple_rolling_mean(&self, column_name : &str, window : usize) -> DataFrame {
+        let idx : usize = self.get_column_index(column_name);
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::simple_rolling_mean },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::simple_rolling_mean },
+            DataTypes::StringType(StringType{value : _}) => { StringType::simple_rolling_mean },
+            DataTypes::NullType(_) => { NullType::simple_rolling_mean },
+        };
+
+        DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![op(&self.columns[idx], window),] }
+    }
+
+    fn numeric_columns(&self) -> Vec<usize> {
+        (0..self.labels.len()).filter(|&ci| self.column_is_float(ci) || self.column_is_integer(ci)).collect()
+    }
+
+    // covariance and correlation use the rows where both columns are
+    // non-null; string columns are treated as all NaN
+    //
+    pub fn cov(&self, x : &str, y : &str) -> Result<DataTypes, DataFrameError> {
+        let (xs, ys) = complete_pairs(&self.columns[self.find_column_index(x)?], &self.columns[self.find_column_index(y)?]);
+        Ok(DataTypes::FloatType(FloatType{value : covariance(&xs, &ys)}))
+    }
+
+    pub fn corr(&self, x : &str, y : &str, method : CorrelationMethod) -> Result<DataTypes, DataFrameError> {
+        let (xs, ys) = complete_pairs(&self.columns[self.find_column_index(x)?], &self.columns[self.find_column_index(y)?]);
+        Ok(DataTypes::FloatType(FloatType{value : correlation(&xs, &ys, method)}))
+    }
+
+    fn pairwise_matrix(&self, op : &dyn Fn(&[f64], &[f64]) -> f64) -> DataFrame {
+        let numeric = self.numeric_columns();
+
+        let mut result = DataFrame::new();
+        result.labels.push(String::from("column"));
+        result.columns.push(numeric.iter().map(|&ci| DataTypes::StringType(StringType{value : self.labels[ci].to_string()})).collect());
+
+        for &cj in numeric.iter() {
+            result.labels.push(self.labels[cj].to_string());
+            result.columns.push(numeric.iter().map(|&ci| {
+                let (xs, ys) = complete_pairs(&self.columns[ci], &self.columns[cj]);
+                DataTypes::FloatType(FloatType{value : op(&xs, &ys)})
+            }).collect());
+        }
+
+        result
+    }
+
+    // square matrix over every numeric column; the first column holds
+    // the row labels
+    //
+    pub fn cov_matrix(&self) -> DataFrame {
+        self.pairwise_matrix(&covariance)
+    }
+
+    pub fn corr_matrix(&self, method : CorrelationMethod) -> DataFrame {
+        self.pairwise_matrix(&|x, y| correlation(x, y, method))
+    }
+
+    // Pearson correlation over every full window of `window` rows,
+    // NaN where a window has fewer than two complete pairs; a window
+    // of 0 is an InvalidArgument
+    //
+    pub fn rolling_corr(&self, x : &str, y : &str, window : usize) -> Result<DataFrame, DataFrameError> {
+        let xs = &self.columns[self.find_column_index(x)?];
+        let ys = &self.columns[self.find_column_index(y)?];
+        if window == 0 {
+            return Err(DataFrameError::InvalidArgument(String::from("rolling_corr needs a window of at least one row")));
+        }
+
+        let values : Series = xs.windows(window).zip(ys.windows(window)).map(|(a, b)| {
+            let (a, b) = complete_pairs(a, b);
+            DataTypes::FloatType(FloatType{value : pearson(&a, &b)})
+        }).collect();
+
+        Ok(DataFrame{ labels : std::vec![format!("{}_{}", x, y),], columns : vec![values,] })
+    }
+
+    // ordinary least squares of `y` on the `x` columns with an
+    // intercept, over the rows where every column is non-null
+    //
+    pub fn ols(&self, y : &str, x : Vec<&str>) -> Result<Regression, DataFrameError> {
+        let yidx = self.find_column_index(y)?;
+        let xidxs : Vec<usize> = x.iter().map(|&c| self.find_column_index(c)).collect::<Result<_, _>>()?;
+
+        for &idx in xidxs.iter().chain(std::iter::once(&yidx)) {
+            if self.column_is_string(idx) {
+                return Err(DataFrameError::InvalidArgument(format!("column '{}' is not numeric", self.labels[idx])));
+            }
+        }
+
+        let rows : Vec<usize> = (0..self.nrows()).filter(|&r|
+            xidxs.iter().chain(std::iter::once(&yidx)).all(|&c| !self.columns[c][r].is_null())
+        ).collect();
+
+        let ys : Vec<f64> = rows.iter().map(|&r| self.columns[yidx][r].fvalue()).collect();
+        let xs : Vec<Vec<f64>> = xidxs.iter().map(|&c| rows.iter().map(|&r| self.columns[c][r].fvalue()).collect()).collect();
+
+        let (fit, residuals) = least_squares(&ys, &xs)?;
+        Ok(self.with_residuals(fit, &rows, &residuals))
+    }
+
+    // bootstrap confidence interval of `aggfunc` over the non-null
+    // values of a column, from `resamples` resamples drawn with a
+    // generator seeded by `seed`
+    //
+    pub fn bootstrap(&self, column_name : &str, aggfunc : Aggregation, method : BootstrapMethod, confidence : f64, resamples : usize, seed : u64) -> Result<ConfidenceInterval, DataFrameError> {
+        check_bootstrap(confidence, resamples)?;
+        aggfunc.check()?;
+        let idx = self.find_column_index(column_name)?;
+        let values : Series = self.columns[idx].iter().filter(|v| !v.is_null()).cloned().collect();
+
+        Ok(bootstrap_interval(&values, aggfunc, method, confidence, resamples, &mut SplitMix64::new(seed)))
+    }
+
+    // linear trend of `value_column` over `time_column`, which holds
+    // numbers or dates; the slope of a date column is per second
+    //
+    pub fn linear_trend(&self, time_column : &str, value_column : &str) -> Result<Regression, DataFrameError> {
+        let points = self.trend_points(time_column, value_column)?;
+        let rows : Vec<usize> = points.iter().map(|p| p.0).collect();
+        let times : Vec<f64> = points.iter().map(|p| p.1).collect();
+        let values : Vec<f64> = points.iter().map(|p| p.2).collect();
+
+        let (fit, residuals) = least_squares(&values, &[times])?;
+        Ok(self.with_residuals(fit, &rows, &residuals))
+    }
+
+    // robust trend as (slope, intercept), see `linear_trend` for units;
+    // quadratic in time and memory, so more than 5000 complete rows is
+    // an InvalidArgument
+    //
+    pub fn theil_sen(&self, time_column : &str, value_column : &str) -> Result<(f64, f64), DataFrameError> {
+        let points = self.trend_points(time_column, value_column)?;
+        if points.len() < 2 {
+            return Err(DataFrameError::InvalidArgument(format!("{} complete rows can not give a trend", points.len())));
+        }
+        if points.len() > THEIL_SEN_MAX_POINTS {
+            return Err(DataFrameError::InvalidArgument(format!("theil_sen takes at most {} complete rows, has {}", THEIL_SEN_MAX_POINTS, points.len())));
+        }
+
+        let times : Vec<f64> = points.iter().map(|p| p.1).collect();
+        let values : Vec<f64> = points.iter().map(|p| p.2).collect();
+        Ok(theil_sen(&times, &values))
+    }
+
+    // (row, time, value) of the rows where both columns are non-null
+    //
+    fn trend_points(&self, time_column : &str, value_column : &str) -> Result<Vec<(usize, f64, f64)>, DataFrameError> {
+        let tidx = self.find_column_index(time_column)?;
+        let vidx = self.find_column_index(value_column)?;
+        if self.column_is_string(vidx) {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' is not numeric", value_column)));
+        }
+
+        let mut points = Vec::new();
+        for (r, (t, v)) in self.columns[tidx].iter().zip(self.columns[vidx].iter()).enumerate() {
+            if t.is_null() || v.is_null() {
+                continue;
+            }
+            let time = t.key_value().ok_or_else(|| DataFrameError::InvalidArgument(format!("'{}' in column '{}' is not a number or date", t.svalue(), time_column)))?;
+            points.push((r, time, v.fvalue()));
+        }
+
+        Ok(points)
+    }
+
+    fn with_residuals(&self, mut fit : Regression, rows : &[usize], residuals : &[f64]) -> Regression {
+        fit.residuals = vec![DataTypes::null(); self.nrows()];
+        for (&r, &e) in rows.iter().zip(residuals.iter()) {
+            fit.residuals[r] = DataTypes::FloatType(FloatType{value : e});
+        }
+        fit
+    }
+
+    // splits `value_column` into trend, seasonal and residual
+    // components for a season of `period` rows. the dates or numbers
+    // of `time_column` must be strictly increasing (NotSorted
+    // otherwise) and the rows are taken to be evenly spaced, one
+    // observation per step of the season; that is not checked, since
+    // calendar steps such as months differ in length. values must not
+    // hold nulls. the classical trend is null for the half period at
+    // either end; the multiplicative loess model decomposes the
+    // logarithm of the (positive) values
+    //
+    pub fn seasonal_decompose(&self, time_column : &str, value_column : &str, period : usize, model : DecompositionModel, method : DecompositionMethod) -> Result<DataFrame, DataFrameError> {
+        let tidx = self.find_column_index(time_column)?;
+        let vidx = self.find_column_index(value_column)?;
+        if self.column_is_string(vidx) {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' is not numeric", value_column)));
+        }
+
+        let mut times : Vec<f64> = Vec::with_capacity(self.nrows());
+        for (r, (t, v)) in self.columns[tidx].iter().zip(self.columns[vidx].iter()).enumerate() {
+            if v.is_null() {
+                return Err(DataFrameError::InvalidArgument(format!("column '{}' has a null in row {}, interpolate it first", value_column, r)));
+            }
+            times.push(t.key_value().ok_or_else(|| DataFrameError::InvalidArgument(format!("'{}' in column '{}' is not a number or date", t.svalue(), time_column)))?);
+        }
+        if times.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(DataFrameError::NotSorted(time_column.to_string()));
+        }
+
+        let x : Vec<f64> = self.columns[vidx].iter().map(|v| v.fvalue()).collect();
+        if period < 2 || x.len() < 2 * period {
+            return Err(DataFrameError::InvalidArgument(format!("{} rows can not be decomposed with period {}", x.len(), period)));
+        }
+        if model == DecompositionModel::Multiplicative && x.iter().any(|&v| v <= 0.0) {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' must be positive for a multiplicative model", value_column)));
+        }
+
+        let (trend, seasonal, residual) = match (method, model) {
+            (DecompositionMethod::Classical, _) => classical_decomposition(&x, period, model),
+            (DecompositionMethod::Loess, DecompositionModel::Additive) => stl_decomposition(&x, period),
+            (DecompositionMethod::Loess, DecompositionModel::Multiplicative) => {
+                let logs : Vec<f64> = x.iter().map(|v| v.ln()).collect();
+                let (t, s, r) = stl_decomposition(&logs, period);
+                let exp = |c : Vec<f64>| c.iter().map(|v| v.exp()).collect::<Vec<f64>>();
+                (exp(t), exp(s), exp(r))
+            },
+        };
+
+        let float = |v : f64| if v.is_nan() { DataTypes::null() } else { DataTypes::FloatType(FloatType{value : v}) };
+        Ok(DataFrame{
+            labels : vec![time_column.to_string(), String::from("observed"), String::from("trend"), String::from("seasonal"), String::from("residual")],
+            columns : vec![
+                self.columns[tidx].clone(),
+                x.iter().map(|&v| float(v)).collect(),
+                trend.iter().map(|&v| float(v)).collect(),
+                seasonal.iter().map(|&v| float(v)).collect(),
+                residual.iter().map(|&v| float(v)).collect(),
+            ],
+        })
+    }
+
+    // non-null values of a numeric column with at least `nlags` + 1
+    // of them, and the normal quantile of a two-sided `confidence`
+    //
+    fn lag_values(&self, column_name : &str, nlags : usize, confidence : f64) -> Result<(Vec<f64>, f64), DataFrameError> {
+        check_confidence(confidence)?;
+        let values = self.numeric_values(self.find_column_index(column_name)?)?;
+        if values.len() <= nlags {
+            return Err(DataFrameError::InvalidArgument(format!("{} values in column '{}' can not give {} lags", values.len(), column_name, nlags)));
+        }
+        Ok((values, hypothesis::normal_ppf(0.5 + confidence / 2.0)))
+    }
+
+    // autocorrelation at lags 0..=nlags of the non-null values of a
+    // column, with bounds from Bartlett's standard errors
+    //
+    pub fn acf(&self, column_name : &str, nlags : usize, confidence : f64) -> Result<DataFrame, DataFrameError> {
+        let (values, z) = self.lag_values(column_name, nlags, confidence)?;
+        let n = values.len() as f64;
+
+        let acf : Vec<f64> = (0..=nlags).map(|k| lagged_correlation(&values, &values, k as isize)).collect();
+        let mut cumulative = 0.0;
+        let errors : Vec<f64> = acf.iter().enumerate().map(|(k, r)| {
+            if k == 0 {
+                return 0.0;
+            }
+            let e = ((1.0 + 2.0 * cumulative) / n).sqrt();
+            cumulative += r * r;
+            e
+        }).collect();
+
+        Ok(correlogram("acf", (0..=nlags as i64).collect(), acf, errors, z))
+    }
+
+    // partial autocorrelation at lags 0..=nlags by Durbin-Levinson,
+    // bounds use the standard error 1/sqrt(n)
+    //
+    pub fn pacf(&self, column_name : &str, nlags : usize, confidence : f64) -> Result<DataFrame, DataFrameError> {
+        let (values, z) = self.lag_values(column_name, nlags, confidence)?;
+        let n = values.len() as f64;
+
+        let acf : Vec<f64> = (0..=nlags).map(|k| lagged_correlation(&values, &values, k as isize)).collect();
+        let mut pacf = vec![1.0];
+        pacf.extend(durbin_levinson(&acf, nlags));
+        let errors : Vec<f64> = (0..=nlags).map(|k| if k == 0 { 0.0 } else { 1.0 / n.sqrt() }).collect();
+
+        Ok(correlogram("pacf", (0..=nlags as i64).collect(), pacf, errors, z))
+    }
+
+    // cross-correlation of x[t] and y[t + lag] for lags -nlags..=nlags
+    // over the rows where both are non-null, bounds use 1/sqrt(n)
+    //
+    pub fn ccf(&self, x : &str, y : &str, nlags : usize, confidence : f64) -> Result<DataFrame, DataFrameError> {
+        check_confidence(confidence)?;
+        let xidx = self.find_column_index(x)?;
+        let yidx = self.find_column_index(y)?;
+        self.numeric_values(xidx)?;
+        self.numeric_values(yidx)?;
+
+        let (xs, ys) = complete_pairs(&self.columns[xidx], &self.columns[yidx]);
+        if xs.len() <= nlags {
+            return Err(DataFrameError::InvalidArgument(format!("{} complete rows can not give {} lags", xs.len(), nlags)));
+        }
+
+        let lags : Vec<i64> = (-(nlags as i64)..=nlags as i64).collect();
+        let values : Vec<f64> = lags.iter().map(|&k| lagged_correlation(&xs, &ys, k as isize)).collect();
+        let errors = vec![1.0 / (xs.len() as f64).sqrt(); lags.len()];
+
+        Ok(correlogram("ccf", lags, values, errors, hypothesis::normal_ppf(0.5 + confidence / 2.0)))
+    }
+
+    pub fn rolling_stddev(&self, column_name : &str, window : usize, ddof : usize) -> DataFrame {
+        let idx : usize = self.get_column_index(column_name);
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::rolling_stddev },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::rolling_stddev },
+            DataTypes::StringType(StringType{value : _}) => { StringType::rolling_stddev },
+            DataTypes::NullType(_) => { NullType::rolling_stddev },
+        };
+
+        DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![op(&self.columns[idx], window, ddof),] }
+    }
+    
+    pub fn rolling_variance(&self, column_name : &str, window : usize, ddof : usize) -> DataFrame {
+        let idx : usize = self.get_column_index(column_name);
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::rolling_variance },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::rolling_variance },
+            DataTypes::StringType(StringType{value : _}) => { StringType::rolling_variance },
+            DataTypes::NullType(_) => { NullType::rolling_variance },
+        };
+
+        DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![op(&self.columns[idx], window, ddof),] }
+    }
+
+    pub fn diff(&self, column_name : &str) -> DataFrame {
+        let idx : usize = self.get_column_index(column_name);
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::diff },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::diff },
+            DataTypes::StringType(StringType{value : _}) => { StringType::diff },
+            DataTypes::NullType(_) => { NullType::diff },
+        };
+
+        DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![op(&self.columns[idx],),] }
+    }
+
+    // ranks of a column, see `RankMethod` and `NullRank`; `pct` gives
+    // percentile ranks in (0, 1]
+    //
+    pub fn rank(&self, column_name : &str, method : RankMethod, ascending : bool, pct : bool, nulls : NullRank) -> Result<DataFrame, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+        Ok(DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![rank_series(&self.columns[idx], method, ascending, pct, nulls),] })
+    }
+
+    // x coordinates for `interpolate`: the values of `time_column`,
+    // which must be non-null and increasing, or the row positions
+    //
+    fn interpolation_index(&self, method : InterpolationMethod, time_column : Option<&str>) -> Result<Vec<f64>, DataFrameError> {
+        if method == InterpolationMethod::Linear || (time_column.is_none() && method != InterpolationMethod::Time) {
+            return Ok((0..self.nrows()).map(|i| i as f64).collect());
+        }
+        let time_column = time_column.ok_or_else(|| DataFrameError::InvalidArgument(String::from("time interpolation needs a time column")))?;
+
+        let tidx = self.find_column_index(time_column)?;
+        let times : Vec<f64> = self.columns[tidx].iter().map(|t|
+            t.key_value().ok_or_else(|| DataFrameError::InvalidArgument(format!("'{}' in column '{}' is not a number or date", t.svalue(), time_column)))
+        ).collect::<Result<_, _>>()?;
+
+        if times.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(DataFrameError::NotSorted(time_column.to_string()));
+        }
+        Ok(times)
+    }
+
+    // fills the nulls of a numeric column, see `InterpolationMethod`;
+    // `time_column` is required by `Time` and gives the coordinates of
+    // the nearest, polynomial and spline methods. at most `limit`
+    // consecutive nulls are filled in `direction`
+    //
+    pub fn interpolate(&self, column_name : &str, method : InterpolationMethod, time_column : Option<&str>, limit : Option<usize>, direction : LimitDirection) -> Result<DataFrame, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+        self.numeric_values(idx)?;
+        let x = self.interpolation_index(method, time_column)?;
+
+        Ok(DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![interpolate_series(&x, &self.columns[idx], method, limit, direction),] })
+    }
+
+    pub fn pct_change(&self, column_name : &str) -> DataFrame {
+        let idx : usize = self.get_column_index(column_name);
+
+        let op = match self.column_type(idx) {
+            DataTypes::FloatType(FloatType{value : _}) => { FloatType::pct_change },
+            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::pct_change },
+            DataTypes::StringType(StringType{value : _}) => { StringType::pct_change },
+            DataTypes::NullType(_) => { NullType::pct_change },
+        };
+
+        DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![op(&self.columns[idx],),] }
+    }
+
+    // row permutation that stably sorts the frame by `column_names`
+    //
+    // `ascending` holds one flag per column, or a single flag applied
+    // to every column; nulls (and NaN) are placed first or last
+    // independent of the sort direction
+    //
+    pub fn argsort(&self, column_names : Vec<&str>, ascending : Vec<bool>, nulls_first : bool) -> Result<Vec<usize>, DataFrameError> {
+        if ascending.len() != 1 && ascending.len() != column_names.len() {
+            return Err(DataFrameError::InvalidArgument(format!("{} ascending flags given for {} columns", ascending.len(), column_names.len())));
+        }
+
+        let keys : Vec<(&Series, bool)> = column_names.iter().enumerate().map(|(i, &x)|
+            Ok((&self.columns[self.find_column_index(x)?], ascending[if ascending.len() == 1 { 0 } else { i }]))
+        ).collect::<Result<_, DataFrameError>>()?;
+
+        let mut order : Vec<usize> = (0..self.nrows()).collect();
+
+        order.sort_by(|&l, &r| {
+            for (column, asc) in keys.iter() {
+                let ordering = match (column[l].is_null(), column[r].is_null()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => if nulls_first { Ordering::Less } else { Ordering::Greater },
+                    (false, true) => if nulls_first { Ordering::Greater } else { Ordering::Less },
+                    (false, false) => if *asc { column[l].cmp(&column[r]) } else { column[r].cmp(&column[l]) },
+                };
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            Ordering::Equal
+        });
+
+        Ok(order)
+    }
+
+    pub fn sort_by(&self, column_names : Vec<&str>, ascending : Vec<bool>, nulls_first : bool) -> Result<DataFrame, DataFrameError> {
+        let order = self.argsort(column_names, ascending, nulls_first)?;
+        Ok(self.take(&order))
+    }
+
+    // the `n` rows with the largest values in `column_names`, ties
+    // keep their original order and rows with a null key are dropped
+    //
+    pub fn nlargest(&self, n : usize, column_names : Vec<&str>) -> Result<DataFrame, DataFrameError> {
+        let order = self.ordered_non_null(column_names, false)?;
+        Ok(self.take(&order[..n.min(order.len())]))
+    }
+
+    pub fn nsmallest(&self, n : usize, column_names : Vec<&str>) -> Result<DataFrame, DataFrameError> {
+        let order = self.ordered_non_null(column_names, true)?;
+        Ok(self.take(&order[..n.min(order.len())]))
+    }
+
+    fn ordered_non_null(&self, column_names : Vec<&str>, ascending : bool) -> Result<Vec<usize>, DataFrameError> {
+        let idxs : Vec<usize> = column_names.iter().map(|&x| self.find_column_index(x)).collect::<Result<_, _>>()?;
+        Ok(self.argsort(column_names, vec![ascending], false)?.into_iter().filter(|&row|
+            idxs.iter().all(|&ci| !self.columns[ci][row].is_null())
+        ).collect())
+    }
+
+    // new frame holding the rows at `rows`, in that order
+    //
+    pub fn take(&self, rows : &[usize]) -> DataFrame {
+        DataFrame{
+            labels : self.labels.clone(),
+            columns : self.columns.iter().map(|c| rows.iter().map(|&r| c[r].clone()).collect()).collect(),
+        }
+    }
+
+    // replaces nulls as described by `FillNa`; with a `limit` at most
+    // that many nulls of each run of consecutive nulls are filled
+    //
+    pub fn fillna(&self, value : FillNa, limit : Option<usize>) -> Result<DataFrame, DataFrameError> {
+        if let FillNa::Columns(ref map) = value {
+            for name in map.keys() {
+                self.find_column_index(name)?;
+            }
+        }
+
+        let limit = limit.unwrap_or(usize::MAX);
+        let columns = self.labels.iter().zip(self.columns.iter()).map(|(label, column)| {
+            let mut filled = column.clone();
+            let mut run = 0;
+            let mut last : Option<&DataTypes> = None;
+
+            let order : Vec<usize> = if let FillNa::Backward = value { (0..column.len()).rev().collect() } else { (0..column.len()).collect() };
+            for i in order {
+                if !column[i].is_null() {
+                    run = 0;
+                    last = Some(&column[i]);
+                    continue;
+                }
+
+                run += 1;
+                let fill = match value {
+                    FillNa::Value(ref v) => Some(v),
+                    FillNa::Columns(ref map) => map.get(label),
+                    FillNa::Forward | FillNa::Backward => last,
+                };
+                if let Some(v) = fill.filter(|_| run <= limit) {
+                    filled[i] = v.clone();
+                }
+            }
+            filled
+        }).collect();
+
+        Ok(DataFrame{ labels : self.labels.clone(), columns })
+    }
+
+    // drops the rows with nulls in the `subset` columns (every column
+    // when None); with a `thresh` a row is kept when it has at least
+    // that many non-null values, whatever `how` is
+    //
+    pub fn dropna(&self, how : DropHow, subset : Option<Vec<&str>>, thresh : Option<usize>) -> Result<DataFrame, DataFrameError> {
+        let idxs : Vec<usize> = match subset {
+            Some(names) => names.iter().map(|&x| self.find_column_index(x)).collect::<Result<_, _>>()?,
+            None => (0..self.columns.len()).collect(),
+        };
+
+        let rows : Vec<usize> = (0..self.nrows()).filter(|&r| {
+            let present = idxs.iter().filter(|&&c| !self.columns[c][r].is_null()).count();
+            match (thresh, how) {
+                (Some(t), _) => present >= t,
+                (None, DropHow::Any) => present == idxs.len(),
+                (None, DropHow::All) => present > 0 || idxs.is_empty(),
+            }
+        }).collect();
+
+        Ok(self.take(&rows))
+    }
+
+    // mask frame with the frame's labels, integer 1 where a value is
+    // null and 0 where it is not
+    //
+    pub fn isnull(&self) -> DataFrame {
+        self.null_mask(true)
+    }
+
+    pub fn notnull(&self) -> DataFrame {
+        self.null_mask(false)
+    }
+
+    fn null_mask(&self, null : bool) -> DataFrame {
+        DataFrame{
+            labels : self.labels.clone(),
+            columns : self.columns.iter().map(|c| c.iter().map(|v|
+                DataTypes::IntegerType(IntegerType{value : if v.is_null() == null { 1 } else { 0 }})
+            ).collect()).collect(),
+        }
+    }
+
+    // number of nulls in every column, in column order
+    //
+    pub fn null_counts(&self) -> Vec<(String, usize)> {
+        self.labels.iter().zip(self.columns.iter()).map(|(l, c)| (l.to_string(), c.iter().filter(|v| v.is_null()).count())).collect()
+    }
+
+    fn find_column_index(&self, column_name : &str) -> Result<usize, DataFrameError> {
+        self.labels.iter().position(|l| *l == column_name ).ok_or_else(|| DataFrameError::ColumnNotFound(column_name.to_string()))
+    }
+
+    fn row_keys(&self, idxs : &[usize]) -> Vec< Vec<DataTypes> > {
+        (0..self.nrows()).map(|row| idxs.iter().map(|&ci| self.columns[ci][row].clone()).collect()).collect()
+    }
+
+    // true for the rows whose values in `subset` (every column when
+    // None) repeat those of another row, except the one `keep` names
+    //
+    pub fn duplicated(&self, subset : Option<Vec<&str>>, keep : Keep) -> Result<Vec<bool>, DataFrameError> {
+        let idxs : Vec<usize> = match subset {
+            Some(names) => names.iter().map(|&x| self.find_column_index(x)).collect::<Result<_, _>>()?,
+            None => (0..self.columns.len()).collect(),
+        };
+
+        let keys = self.row_keys(&idxs);
+        let mut counts : HashMap<&Vec<DataTypes>, usize> = HashMap::new();
+        for key in keys.iter() {
+            *counts.entry(key).or_insert(0) += 1;
+        }
+
+        let mut seen : HashMap<&Vec<DataTypes>, usize> = HashMap::new();
+        Ok(keys.iter().map(|key| {
+            let n = seen.entry(key).or_insert(0);
+            *n += 1;
+            match keep {
+                Keep::First => *n > 1,
+                Keep::Last => *n < counts[key],
+                Keep::None => counts[key] > 1,
+            }
+        }).collect())
+    }
+
+    pub fn drop_duplicates(&self, subset : Option<Vec<&str>>, keep : Keep) -> Result<DataFrame, DataFrameError> {
+        let mask = self.duplicated(subset, keep)?;
+        let rows : Vec<usize> = (0..mask.len()).filter(|&r| !mask[r]).collect();
+        Ok(self.take(&rows))
+    }
+
+    // distinct values of a column in order of first appearance; null
+    // and NaN count as one null, listed where the first of them is
+    //
+    pub fn unique(&self, column_name : &str) -> Result<DataFrame, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+        let mut seen : HashSet<DataTypes> = HashSet::new();
+        let values : Series = self.columns[idx].iter()
+            .map(|v| if v.is_null() { DataTypes::null() } else { v.clone() })
+            .filter(|v| seen.insert(v.clone())).collect();
+
+        Ok(DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![values,] })
+    }
+
+    // number of distinct non-null values in every column, in column order
+    //
+    pub fn nunique(&self) -> Vec<(String, usize)> {
+        self.labels.iter().zip(self.columns.iter()).map(|(l, c)| {
+            let mut seen : HashSet<&DataTypes> = HashSet::new();
+            (l.to_string(), c.iter().filter(|v| !v.is_null() && seen.insert(v)).count())
+        }).collect()
+    }
+
+    // hash join of `self` (left) with `other` (right) on the columns
+    // named in `on`, which must exist in both frames
+    //
+    // key columns appear once in the result; other columns present
+    // in both frames get `suffixes.0` (left) and `suffixes.1` (right)
+    // appended. inner and left joins keep the left row order, right
+    // joins the right row order and outer joins append unmatched
+    // right rows after the left rows. semi and anti joins return
+    // left rows only, cross joins ignore `on` and have no keys to
+    // validate, so they only accept `JoinValidate::ManyToMany`
+    //
+    pub fn join(&self, other : &DataFrame, on : Vec<&str>, how : JoinType, suffixes : (&str, &str), validate : JoinValidate) -> Result<DataFrame, DataFrameError> {
+        if how == JoinType::Cross && validate != JoinValidate::ManyToMany {
+            return Err(DataFrameError::InvalidArgument(format!("a cross join has no keys to validate as {:?}", validate)));
+        }
+
+        let lkeys : Vec<usize> = if how == JoinType::Cross { Vec::new() } else {
+            on.iter().map(|&x| self.find_column_index(x)).collect::<Result<_, _>>()?
+        };
+        let rkeys : Vec<usize> = if how == JoinType::Cross { Vec::new() } else {
+            on.iter().map(|&x| other.find_column_index(x)).collect::<Result<_, _>>()?
+        };
+
+        let lrows = self.row_keys(&lkeys);
+        let rrows = other.row_keys(&rkeys);
+
+        let mut table : HashMap< Vec<DataTypes>, Vec<usize> > = HashMap::new();
+        for (row, key) in rrows.iter().enumerate() {
+            table.entry(key.clone()).or_default().push(row);
+        }
+
+        if validate == JoinValidate::OneToOne || validate == JoinValidate::OneToMany {
+            let mut seen : HashMap< &Vec<DataTypes>, usize > = HashMap::new();
+            if let Some(key) = lrows.iter().find(|k| { let c = seen.entry(*k).or_insert(0); *c += 1; *c > 1 }) {
+                return Err(DataFrameError::MergeValidation(format!("left keys are not unique, {:?} repeats", key.iter().map(|k| k.svalue()).collect::<Vec<String>>())));
+            }
+        }
+
+        if validate == JoinValidate::OneToOne || validate == JoinValidate::ManyToOne {
+            if let Some((key, _)) = table.iter().find(|(_, rows)| rows.len() > 1) {
+                return Err(DataFrameError::MergeValidation(format!("right keys are not unique, {:?} repeats", key.iter().map(|k| k.svalue()).collect::<Vec<String>>())));
+            }
+        }
+
+        let mut pairs : Vec<(Option<usize>, Option<usize>)> = Vec::new();
+        match how {
+            JoinType::Inner | JoinType::Left | JoinType::Outer => {
+                let mut matched = vec![false; rrows.len()];
+                for (lrow, key) in lrows.iter().enumerate() {
+                    match table.get(key) {
+                        Some(rows) => {
+                            for &rrow in rows {
+                                matched[rrow] = true;
+                                pairs.push((Some(lrow), Some(rrow)));
+                            }
+                        },
+                        None => if how != JoinType::Inner { pairs.push((Some(lrow), None)); },
+                    }
+                }
+
+                if how == JoinType::Outer {
+                    pairs.extend(matched.iter().enumerate().filter(|(_, &m)| !m).map(|(rrow, _)| (None, Some(rrow))));
+                }
+            },
+            JoinType::Right => {
+                let mut ltable : HashMap< &Vec<DataTypes>, Vec<usize> > = HashMap::new();
+                for (row, key) in lrows.iter().enumerate() {
+                    ltable.entry(key).or_default().push(row);
+                }
+
+                for (rrow, key) in rrows.iter().enumerate() {
+                    match ltable.get(key) {
+                        Some(rows) => pairs.extend(rows.iter().map(|&lrow| (Some(lrow), Some(rrow)))),
+                        None => pairs.push((None, Some(rrow))),
+                    }
+                }
+            },
+            JoinType::Semi | JoinType::Anti => {
+                let keep = how == JoinType::Semi;
+                let rows : Vec<usize> = lrows.iter().enumerate().filter(|(_, key)| table.contains_key(*key) == keep).map(|(row, _)| row).collect();
+                return Ok(self.take(&rows));
+            },
+            JoinType::Cross => {
+                for lrow in 0..lrows.len() {
+                    pairs.extend((0..rrows.len()).map(|rrow| (Some(lrow), Some(rrow))));
+                }
+            },
+        }
+
+        let mut result = DataFrame::new();
+
+        for (ci, label) in self.labels.iter().enumerate() {
+            let values : Series = match lkeys.iter().position(|&k| k == ci) {
+                Some(k) => pairs.iter().map(|&(l, r)| match (l, r) {
+                    (Some(l), _) => self.columns[ci][l].clone(),
+                    (None, Some(r)) => other.columns[rkeys[k]][r].clone(),
+                    (None, None) => DataTypes::null(),
+                }).collect(),
+                None => pairs.iter().map(|&(l, _)| l.map_or(DataTypes::null(), |l| self.columns[ci][l].clone())).collect(),
+            };
+
+            let overlaps = !lkeys.contains(&ci) && other.labels.iter().enumerate().any(|(i, l)| l == label && !rkeys.contains(&i));
+            result.labels.push(if overlaps { format!("{}{}", label, suffixes.0) } else { label.to_string() });
+            result.columns.push(values);
+        }
+
+        for (ci, label) in other.labels.iter().enumerate() {
+            if rkeys.contains(&ci) {
+                continue;
+            }
+
+            let overlaps = self.labels.iter().enumerate().any(|(i, l)| l == label && !lkeys.contains(&i));
+            result.labels.push(if overlaps { format!("{}{}", label, suffixes.1) } else { label.to_string() });
+            result.columns.push(pairs.iter().map(|&(_, r)| r.map_or(DataTypes::null(), |r| other.columns[ci][r].clone())).collect());
+        }
+
+        Ok(result)
+    }
+
+    // as-of join: every left row is matched to at most one right row
+    // whose `on` key is the closest in `direction`, looking only at
+    // right rows with the same values in the `by` columns
+    //
+    // both frames must be sorted by `on`. `tolerance` bounds the key
+    // distance, measured in seconds for datetime strings. the result
+    // has every left row in order, followed by the right columns
+    // other than `on`/`by`; unmatched rows hold nulls
+    //
+    pub fn join_asof(&self, other : &DataFrame, on : &str, by : Vec<&str>, direction : AsofDirection, tolerance : Option<f64>, suffixes : (&str, &str)) -> Result<DataFrame, DataFrameError> {
+        let lon = self.find_column_index(on)?;
+        let ron = other.find_column_index(on)?;
+        let lby : Vec<usize> = by.iter().map(|&x| self.find_column_index(x)).collect::<Result<_, _>>()?;
+        let rby : Vec<usize> = by.iter().map(|&x| other.find_column_index(x)).collect::<Result<_, _>>()?;
+
+        // keys are compared as numbers or timestamps, so datetime
+        // strings order by time rather than by their text
+        let key_values = |column : &Series| -> Result<Vec<Option<f64>>, DataFrameError> {
+            column.iter().map(|k| if k.is_null() { Ok(None) } else {
+                k.key_value().map(Some).ok_or_else(|| DataFrameError::InvalidArgument(format!("'{}' in column '{}' is not a number or date", k.svalue(), on)))
+            }).collect()
+        };
+        let lkeys = key_values(&self.columns[lon])?;
+        let rkeys = key_values(&other.columns[ron])?;
+
+        for keys in [&lkeys, &rkeys] {
+            let present : Vec<f64> = keys.iter().flatten().copied().collect();
+            if present.windows(2).any(|w| w[0] > w[1]) {
+                return Err(DataFrameError::NotSorted(on.to_string()));
+            }
+        }
+
+        let mut partitions : HashMap< Vec<DataTypes>, Vec<usize> > = HashMap::new();
+        for (row, key) in other.row_keys(&rby).into_iter().enumerate() {
+            if rkeys[row].is_some() {
+                partitions.entry(key).or_default().push(row);
+            }
+        }
+
+        let rkey = |r : usize| rkeys[r].unwrap_or(f64::NAN);
+        let mut matches : Vec<Option<usize>> = Vec::new();
+
+        for (lrow, part) in self.row_keys(&lby).iter().enumerate() {
+            let (rows, key) = match (partitions.get(part), lkeys[lrow]) {
+                (Some(rows), Some(key)) => (rows, key),
+                _ => { matches.push(None); continue; }
+            };
+
+            let after = rows.partition_point(|&r| rkey(r) < key);
+            let upto = rows.partition_point(|&r| rkey(r) <= key);
+            let backward = if upto > 0 { Some(rows[upto - 1]) } else { None };
+            let forward = rows.get(after).copied();
+
+            let found = match direction {
+                AsofDirection::Backward => backward,
+                AsofDirection::Forward => forward,
+                AsofDirection::Nearest => match (backward, forward) {
+                    (Some(b), Some(f)) => if rkey(f) - key < key - rkey(b) { Some(f) } else { Some(b) },
+                    (b, f) => b.or(f),
+                },
+            };
+
+            matches.push(match (found, tolerance) {
+                (Some(r), Some(tol)) => if (rkey(r) - key).abs() <= tol { Some(r) } else { None },
+                (found, None) => found,
+                (None, _) => None,
+            });
+        }
+
+        let mut result = DataFrame::new();
+        let rskip : Vec<usize> = rby.iter().copied().chain(std::iter::once(ron)).collect();
+        let lskip : Vec<usize> = lby.iter().copied().chain(std::iter::once(lon)).collect();
+
+        for (ci, label) in self.labels.iter().enumerate() {
+            let overlaps = !lskip.contains(&ci) && other.labels.iter().enumerate().any(|(i, l)| l == label && !rskip.contains(&i));
+            result.labels.push(if overlaps { format!("{}{}", label, suffixes.0) } else { label.to_string() });
+            result.columns.push(self.columns[ci].clone());
+        }
+
+        for (ci, label) in other.labels.iter().enumerate() {
+            if rskip.contains(&ci) {
+                continue;
+            }
+
+            let overlaps = self.labels.iter().enumerate().any(|(i, l)| l == label && !lskip.contains(&i));
+            result.labels.push(if overlaps { format!("{}{}", label, suffixes.1) } else { label.to_string() });
+            result.columns.push(matches.iter().map(|m| m.map_or(DataTypes::null(), |r| other.columns[ci][r].clone())).collect());
+        }
+
+        Ok(result)
+    }
+
+    // `bins` equal width edges spanning `min..=max`; the lowest edge is
+    // moved down by 0.1% of the range so the minimum lands in a bin
+    //
+    fn equal_width_edges(min : f64, max : f64, bins : usize) -> Vec<f64> {
+        let (min, max) = if min == max { (min - 0.5, max + 0.5) } else { (min, max) };
+        let mut edges = DataFrame::linspace(min, max, bins);
+        edges[0] -= (max - min) * 0.001;
+        edges
+    }
+
+    fn linspace(min : f64, max : f64, bins : usize) -> Vec<f64> {
+        let width = (max - min) / bins as f64;
+        let mut edges : Vec<f64> = (0..=bins).map(|i| min + width * i as f64).collect();
+        edges[bins] = max;
+        edges
+    }
+
+    // non-null values of a numeric column; any string cell, not just
+    // a leading one, makes the column non-numeric
+    //
+    fn numeric_values(&self, idx : usize) -> Result<Vec<f64>, DataFrameError> {
+        if let Some(row) = self.columns[idx].iter().position(|v| matches!(v, DataTypes::StringType(_))) {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' is not numeric, row {} is a string", self.labels[idx], row)));
+        }
+
+        Ok(self.columns[idx].iter().filter(|v| !v.is_null()).map(|v| v.fvalue()).collect())
+    }
+
+    fn check_edges(edges : &[f64]) -> Result<(), DataFrameError> {
+        if edges.len() < 2 || edges.windows(2).any(|w| w[0] >= w[1] || w[0].is_nan() || w[1].is_nan()) {
+            return Err(DataFrameError::InvalidArgument(String::from("bin edges must be at least two strictly increasing values")));
+        }
+        Ok(())
+    }
+
+    // labels each row with its bin, null outside of every bin
+    //
+    fn categorize(&self, idx : usize, edges : &[f64], labels : Option<Vec<&str>>, right : bool, include_lowest : bool) -> Result<DataFrame, DataFrameError> {
+        let names : Vec<String> = match labels {
+            Some(labels) if labels.len() != edges.len() - 1 => {
+                return Err(DataFrameError::InvalidArgument(format!("{} labels given for {} bins", labels.len(), edges.len() - 1)));
+            },
+            Some(labels) => labels.iter().map(|l| l.to_string()).collect(),
+            None => edges.windows(2).enumerate().map(|(i, w)| {
+                let label = DataFrame::interval_label(w[0], w[1], right);
+                if include_lowest && i == 0 && right { label.replacen('(', "[", 1) } else { label }
+            }).collect(),
+        };
+
+        let values : Series = self.columns[idx].iter().map(|v| {
+            let bin = if v.is_null() { None } else { DataFrame::bin_index(edges, v.fvalue(), right, include_lowest) };
+            bin.map_or(DataTypes::null(), |b| DataTypes::StringType(StringType{value : names[b].to_string()}))
+        }).collect();
+
+        Ok(DataFrame{ labels : std::vec![self.labels[idx].to_string(),], columns : vec![values,] })
+    }
+
+    // bins a numeric column into string labelled intervals, usable as
+    // a `group_by` key; `right` closes the intervals on the right.
+    // `Bins::Count` spans the column's range and widens the first bin
+    // to take in the minimum, values outside explicit edges are null.
+    // `labels`, when given, name the bins in order
+    //
+    pub fn cut(&self, column_name : &str, bins : Bins, labels : Option<Vec<&str>>, right : bool) -> Result<DataFrame, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+        let values = self.numeric_values(idx)?;
+
+        let edges = match bins {
+            Bins::Count(0) => { return Err(DataFrameError::InvalidArgument(String::from("cut needs at least one bin"))); },
+            Bins::Count(_) if values.is_empty() => { return Err(DataFrameError::InvalidArgument(format!("column '{}' has no values to bin", column_name))); },
+            Bins::Count(n) => {
+                let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                if right {
+                    DataFrame::equal_width_edges(min, max, n)
+                } else {
+                    // widen the last bin instead so the maximum is inside
+                    let (min, max) = if min == max { (min - 0.5, max + 0.5) } else { (min, max) };
+                    let mut edges = DataFrame::linspace(min, max, n);
+                    edges[n] += (max - min) * 0.001;
+                    edges
+                }
+            },
+            Bins::Edges(edges) => { DataFrame::check_edges(&edges)?; edges },
+        };
+
+        self.categorize(idx, &edges, labels, right, false)
+    }
+
+    // equal frequency binning: the edges are quantiles of the column,
+    // `Bins::Count(n)` gives n bins and `Bins::Edges` lists quantile
+    // levels. repeated edges, from heavily tied values, are collapsed
+    // into one when `drop_duplicate_edges` is set (fewer bins, so
+    // `labels` must match the collapsed count) and an error otherwise
+    //
+    pub fn qcut(&self, column_name : &str, bins : Bins, labels : Option<Vec<&str>>, drop_duplicate_edges : bool) -> Result<DataFrame, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+        let mut values = self.numeric_values(idx)?;
+
+        let levels : Vec<f64> = match bins {
+            Bins::Count(0) => { return Err(DataFrameError::InvalidArgument(String::from("qcut needs at least one bin"))); },
+            Bins::Count(n) => (0..=n).map(|i| i as f64 / n as f64).collect(),
+            Bins::Edges(levels) => levels,
+        };
+
+        if levels.iter().any(|q| !(0.0..=1.0).contains(q)) {
+            return Err(DataFrameError::InvalidArgument(String::from("qcut levels must lie in [0, 1]")));
+        }
+        if values.is_empty() {
+            return Err(DataFrameError::InvalidArgument(format!("column '{}' has no values to bin", column_name)));
+        }
+
+        let mut edges : Vec<f64> = levels.iter().map(|&q| select_quantile(&mut values, q, QuantileInterpolation::Linear)).collect();
+        if drop_duplicate_edges {
+            edges.dedup();
+        }
+        DataFrame::check_edges(&edges)?;
+
+        self.categorize(idx, &edges, labels, true, true)
+    }
+
+    // counts of the non-null values per bin; bins are half open
+    // [a, b) except the last, which also holds its right edge.
+    // `Bins::Count` spans exactly the column's range
+    //
+    pub fn histogram(&self, column_name : &str, bins : Bins) -> Result<(Vec<f64>, Vec<usize>), DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+        let values = self.numeric_values(idx)?;
+
+        let edges = match bins {
+            Bins::Count(0) => { return Err(DataFrameError::InvalidArgument(String::from("histogram needs at least one bin"))); },
+            Bins::Count(n) if values.is_empty() => DataFrame::linspace(0.0, 1.0, n),
+            Bins::Count(n) => {
+                let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                if min == max { DataFrame::linspace(min - 0.5, max + 0.5, n) } else { DataFrame::linspace(min, max, n) }
+            },
+            Bins::Edges(edges) => { DataFrame::check_edges(&edges)?; edges },
+        };
+
+        let mut counts = vec![0; edges.len() - 1];
+        for &v in values.iter() {
+            if let Some(b) = DataFrame::bin_index(&edges, v, false, true) {
+                counts[b] += 1;
+            }
+        }
+
+        Ok((edges, counts))
+    }
+
+    // bin of `value` for edges[i] < value <= edges[i+1] (`right`) or
+    // edges[i] <= value < edges[i+1]; `include_extreme` also admits the
+    // outermost edge that the closed side would exclude
+    //
+    fn bin_index(edges : &[f64], value : f64, right : bool, include_extreme : bool) -> Option<usize> {
+        if value.is_nan() || edges.len() < 2 {
+            return None;
+        }
+
+        let last = edges.len() - 1;
+        if include_extreme && right && value == edges[0] {
+            return Some(0);
+        }
+        if include_extreme && !right && value == edges[last] {
+            return Some(last - 1);
+        }
+
+        let pos = if right {
+            edges.partition_point(|&e| e < value)
+        } else {
+            edges.partition_point(|&e| e <= value)
+        };
+
+        if pos == 0 || pos > last { None } else { Some(pos - 1) }
+    }
+
+    fn interval_label(lo : f64, hi : f64, right : bool) -> String {
+        let round = |x : f64| (x * 1000.0).round() / 1000.0;
+        if right {
+            format!("({}, {}]", round(lo), round(hi))
+        } else {
+            format!("[{}, {})", round(lo), round(hi))
+        }
+    }
+
+    // frequency of each distinct non-null value in `column_name`
+    //
+    // `normalize` reports proportions instead of counts, `sort` orders
+    // by descending frequency instead of by value, and `bins` counts a
+    // numeric column over that many equal width intervals instead
+    //
+    pub fn value_counts(&self, column_name : &str, normalize : bool, sort : bool, bins : Option<usize>) -> Result<DataFrame, DataFrameError> {
+        let idx = self.find_column_index(column_name)?;
+        let column = &self.columns[idx];
+
+        let (keys, counts) : (Series, Vec<usize>) = match bins {
+            Some(bins) => {
+                let values = self.numeric_values(idx)?;
+                if bins == 0 {
+                    return Err(DataFrameError::InvalidArgument(format!("column '{}' can not be split into {} bins", column_name, bins)));
+                }
+
+                let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                let edges = if values.is_empty() { Vec::new() } else { DataFrame::equal_width_edges(min, max, bins) };
+
+                let mut counts = vec![0; edges.len().saturating_sub(1)];
+                for &v in values.iter() {
+                    if let Some(b) = DataFrame::bin_index(&edges, v, true, false) {
+                        counts[b] += 1;
+                    }
+                }
+
+                let keys = edges.windows(2).map(|w| DataTypes::StringType(StringType{value : DataFrame::interval_label(w[0], w[1], true)})).collect();
+                (keys, counts)
+            },
+            None => {
+                let mut tally : HashMap<&DataTypes, usize> = HashMap::new();
+                for v in column.iter().filter(|v| !v.is_null()) {
+                    *tally.entry(v).or_insert(0) += 1;
+                }
+
+                let mut pairs : Vec<(&DataTypes, usize)> = tally.into_iter().collect();
+                pairs.sort_by(|l, r| l.0.cmp(r.0));
+                (pairs.iter().map(|(k, _)| (*k).clone()).collect(), pairs.iter().map(|(_, c)| *c).collect())
+            },
+        };
+
+        let mut order : Vec<usize> = (0..keys.len()).collect();
+        if sort {
+            order.sort_by(|&l, &r| counts[r].cmp(&counts[l]));
+        }
+
+        let total : usize = counts.iter().sum();
+        let mut result = DataFrame::new();
+        result.labels.push(column_name.to_string());
+        result.columns.push(order.iter().map(|&i| keys[i].clone()).collect());
+
+        if normalize {
+            result.labels.push(String::from("proportion"));
+            result.columns.push(order.iter().map(|&i| DataTypes::FloatType(FloatType{value : counts[i] as f64 / total as f64})).collect());
+        }
+        else {
+            result.labels.push(String::from("count"));
+            result.columns.push(order.iter().map(|&i| DataTypes::IntegerType(IntegerType{value : counts[i] as i64})).collect());
+        }
+
+        Ok(result)
+    }
+
+    // frequency table of the `rows` value combinations against the
+    // `columns` value combinations; rows with a null key are skipped
+    //
+    // the result has one key column per entry in `rows` followed by one
+    // count column per distinct `columns` combination, labelled with
+    // the values joined by '_'. `margins` adds "All" totals and
+    // `normalize` turns counts into proportions of the grand total,
+    // the row total or the column total
+    //
+    pub fn crosstab(&self, rows : Vec<&str>, columns : Vec<&str>, margins : bool, normalize : Normalize) -> Result<DataFrame, DataFrameError> {
+        let ridxs : Vec<usize> = rows.iter().map(|&x| self.find_column_index(x)).collect::<Result<_, _>>()?;
+        let cidxs : Vec<usize> = columns.iter().map(|&x| self.find_column_index(x)).collect::<Result<_, _>>()?;
+
+        let rkeys = self.row_keys(&ridxs);
+        let ckeys = self.row_keys(&cidxs);
+        let valid : Vec<usize> = (0..self.nrows()).filter(|&r|
+            rkeys[r].iter().chain(ckeys[r].iter()).all(|v| !v.is_null())
+        ).collect();
+
+        let mut row_values : Vec<&Vec<DataTypes>> = valid.iter().map(|&r| &rkeys[r]).collect();
+        row_values.sort();
+        row_values.dedup();
+        let mut col_values : Vec<&Vec<DataTypes>> = valid.iter().map(|&r| &ckeys[r]).collect();
+        col_values.sort();
+        col_values.dedup();
+
+        let row_pos : HashMap<&Vec<DataTypes>, usize> = row_values.iter().enumerate().map(|(i, k)| (*k, i)).collect();
+        let col_pos : HashMap<&Vec<DataTypes>, usize> = col_values.iter().enumerate().map(|(i, k)| (*k, i)).collect();
+
+        let extra = if margins { 1 } else { 0 };
+        let nrows = row_values.len() + extra;
+        let ncols = col_values.len() + extra;
+        let mut counts : Vec<Vec<f64>> = vec![vec![0.0; nrows]; ncols];
+
+        for &r in valid.iter() {
+            let (i, j) = (row_pos[&rkeys[r]], col_pos[&ckeys[r]]);
+            counts[j][i] += 1.0;
+            if margins {
+                counts[ncols - 1][i] += 1.0;
+                counts[j][nrows - 1] += 1.0;
+                counts[ncols - 1][nrows - 1] += 1.0;
+            }
+        }
+
+        let grand_total = valid.len() as f64;
+        let row_totals : Vec<f64> = (0..nrows).map(|i| counts.iter().take(col_values.len()).map(|c| c[i]).sum()).collect();
+        let col_totals : Vec<f64> = counts.iter().map(|c| c.iter().take(row_values.len()).sum()).collect();
+
+        let mut result = DataFrame::new();
+        for (k, &ri) in ridxs.iter().enumerate() {
+            let mut keys : Series = row_values.iter().map(|v| v[k].clone()).collect();
+            if margins {
+                keys.push(DataTypes::StringType(StringType{value : String::from(if k == 0 { "All" } else { "" })}));
+            }
+            result.labels.push(self.labels[ri].to_string());
+            result.columns.push(keys);
+        }
+
+        for (j, column) in counts.iter().enumerate() {
+            let label = if j < col_values.len() {
+                col_values[j].iter().map(|v| v.svalue()).collect::<Vec<String>>().join("_")
+            } else {
+                String::from("All")
+            };
+
+            result.labels.push(label);
+            result.columns.push(column.iter().enumerate().map(|(i, &c)| match normalize {
+                Normalize::Off => DataTypes::IntegerType(IntegerType{value : c as i64}),
+                Normalize::All => DataTypes::FloatType(FloatType{value : c / grand_total}),
+                Normalize::Rows => DataTypes::FloatType(FloatType{value : c / row_totals[i]}),
+                Normalize::Columns => DataTypes::FloatType(FloatType{value : c / col_totals[j]}),
+            }).collect());
+        }
+
+        Ok(result)
+    }
+
+    // summary of every column as a frame with one row per statistic
+    //
+    // numeric columns report count, null count, mean, std (n-1), min,
+    // 25%/50%/75% quantiles and max, taken over the float and integer
+    // cells only so a stray string does not poison them; string columns report count, null
+    // count, unique, top (most frequent value) and freq. statistics
+    // that do not apply to a column are null. the statistic names are
+    // labelled "statistic", prefixed with underscores when a column of
+    // that name exists
+    //
+    pub fn describe(&self) -> DataFrame {
+        let numeric_stats = ["mean", "std", "min", "25%", "50%", "75%", "max"];
+        let string_stats = ["unique", "top", "freq"];
+
+        let has_numeric = (0..self.labels.len()).any(|ci| !self.column_is_string(ci));
+        let has_string = (0..self.labels.len()).any(|ci| self.column_is_string(ci));
+
+        let mut stats : Vec<&str> = vec!["count", "null"];
+        if has_numeric { stats.extend(numeric_stats.iter()); }
+        if has_string { stats.extend(string_stats.iter()); }
+
+        let mut name = String::from("statistic");
+        while self.labels.contains(&name) {
+            name.insert(0, '_');
+        }
+
+        let mut result = DataFrame::new();
+        result.labels.push(name);
+        result.columns.push(stats.iter().map(|s| DataTypes::StringType(StringType{value : s.to_string()})).collect());
+
+        let float = |value : f64| DataTypes::FloatType(FloatType{value});
+        let integer = |value : usize| DataTypes::IntegerType(IntegerType{value : value as i64});
+
+        for (ci, column) in self.columns.iter().enumerate() {
+            let present : Vec<&DataTypes> = column.iter().filter(|v| !v.is_null()).collect();
+            let mut cells : HashMap<&str, DataTypes> = HashMap::new();
+            cells.insert("count", integer(present.len()));
+            cells.insert("null", integer(column.len() - present.len()));
+
+            if self.column_is_string(ci) {
+                let mut tally : HashMap<&DataTypes, usize> = HashMap::new();
+                for v in present.iter() {
+                    *tally.entry(v).or_insert(0) += 1;
+                }
+
+                cells.insert("unique", integer(tally.len()));
+                if let Some((top, freq)) = tally.iter().max_by(|l, r| l.1.cmp(r.1).then(r.0.cmp(l.0))) {
+                    cells.insert("top", (*top).clone());
+                    cells.insert("freq", integer(*freq));
+                }
+            }
+            else {
+                let numeric : Vec<&DataTypes> = present.iter().filter(|v| matches!(v, DataTypes::FloatType(_) | DataTypes::IntegerType(_))).cloned().collect();
+                if !numeric.is_empty() {
+                    let mut values = present_values(column);
+
+                    let moments = Moments::new(column);
+
+                    cells.insert("mean", float(moments.mean));
+                    cells.insert("std", float(moments.variance(1).sqrt()));
+                    cells.insert("min", (*numeric.iter().min().unwrap()).clone());
+                    cells.insert("25%", float(select_quantile(&mut values, 0.25, QuantileInterpolation::Linear)));
+                    cells.insert("50%", float(select_quantile(&mut values, 0.5, QuantileInterpolation::Linear)));
+                    cells.insert("75%", float(select_quantile(&mut values, 0.75, QuantileInterpolation::Linear)));
+                    cells.insert("max", (*numeric.iter().max().unwrap()).clone());
+                }
+            }
+
+            result.labels.push(self.labels[ci].to_string());
+            result.columns.push(stats.iter().map(|s| cells.remove(s).unwrap_or_else(DataTypes::null)).collect());
+        }
+
+        result
+    }
+
+    // keys of a pivot over the rows whose `index` and `columns` cells
+    // are both non-null, rows with a null key are dropped as pandas
+    // does. returns those rows, the sorted distinct index and columns
+    // keys and the column labels; two keys with the same label (1 and
+    // 1.0), a key taking the index label or, with `margins`, "All" are
+    // an InvalidArgument
+    //
+    fn pivot_keys(&self, ii : usize, ci : usize, margins : bool) -> Result<(Vec<usize>, Series, Series, Vec<String>), DataFrameError> {
+        let kept : Vec<usize> = (0..self.nrows()).filter(|&r| !self.columns[ii][r].is_null() && !self.columns[ci][r].is_null()).collect();
+        let sorted_unique = |column : &Series| {
+            let mut keys : Series = kept.iter().map(|&r| column[r].clone()).collect();
+            keys.sort();
+            keys.dedup();
+            keys
+        };
+        let rows = sorted_unique(&self.columns[ii]);
+        let cols = sorted_unique(&self.columns[ci]);
+
+        let mut taken : HashSet<String> = HashSet::new();
+        taken.insert(self.labels[ii].to_string());
+        if margins {
+            taken.insert(String::from("All"));
+            if rows.iter().any(|r| r.svalue() == "All") {
+                return Err(DataFrameError::InvalidArgument(String::from("index key 'All' collides with the margins row")));
+            }
+        }
+
+        let mut labels : Vec<String> = Vec::new();
+        for key in cols.iter() {
+            let label = key.svalue();
+            if !taken.insert(label.clone()) {
+                return Err(DataFrameError::InvalidArgument(format!("column key '{}' collides with another column label", label)));
+            }
+            labels.push(label);
+        }
+
+        Ok((kept, rows, cols, labels))
+    }
+
+    // reshapes long data to wide: one row per distinct `index` value,
+    // one column per distinct `columns` value, cells taken from
+    // `values`; keys are sorted and a repeated pair is an error, see
+    // `pivot_keys` for null keys and label collisions
+    //
+    pub fn pivot(&self, index : &str, columns : &str, values : &str) -> Result<DataFrame, DataFrameError> {
+        let ii = self.find_column_index(index)?;
+        let ci = self.find_column_index(columns)?;
+        let vi = self.find_column_index(values)?;
+
+        let (kept, rows, cols, labels) = self.pivot_keys(ii, ci, false)?;
+        let row_pos : HashMap<&DataTypes, usize> = rows.iter().enumerate().map(|(i, k)| (k, i)).collect();
+        let col_pos : HashMap<&DataTypes, usize> = cols.iter().enumerate().map(|(i, k)| (k, i)).collect();
+
+        let mut cells : Vec<Series> = vec![vec![DataTypes::null(); rows.len()]; cols.len()];
+        let mut filled : Vec<Vec<bool>> = vec![vec![false; rows.len()]; cols.len()];
+
+        for &row in kept.iter() {
+            let r = row_pos[&self.columns[ii][row]];
+            let c = col_pos[&self.columns[ci][row]];
+            if filled[c][r] {
+                return Err(DataFrameError::InvalidArgument(format!("pivot has more than one value for ({}, {})", rows[r].svalue(), cols[c].svalue())));
+            }
+
+            filled[c][r] = true;
+            cells[c][r] = self.columns[vi][row].clone();
+        }
+
+        let mut result = DataFrame::new();
+        result.labels.push(index.to_string());
+        result.columns.push(rows);
+        result.labels.extend(labels);
+        result.columns.extend(cells);
+        Ok(result)
+    }
+
+    // like `pivot`, but every (index, columns) cell is reduced with
+    // `aggfunc`; `margins` adds an "All" row and column aggregating
+    // each full row, column and every value with non-null keys
+    //
+    pub fn pivot_table(&self, index : &str, columns : &str, values : &str, aggfunc : Aggregation, margins : bool) -> Result<DataFrame, DataFrameError> {
+        aggfunc.check()?;
+        let ii = self.find_column_index(index)?;
+        let ci = self.find_column_index(columns)?;
+        let vi = self.find_column_index(values)?;
+
+        let (kept, mut rows, cols, mut labels) = self.pivot_keys(ii, ci, margins)?;
+        let row_pos : HashMap<&DataTypes, usize> = rows.iter().enumerate().map(|(i, k)| (k, i)).collect();
+        let col_pos : HashMap<&DataTypes, usize> = cols.iter().enumerate().map(|(i, k)| (k, i)).collect();
+
+        let mut groups : Vec<Vec<Series>> = vec![vec![Series::new(); rows.len()]; cols.len()];
+        for &row in kept.iter() {
+            let r = row_pos[&self.columns[ii][row]];
+            let c = col_pos[&self.columns[ci][row]];
+            groups[c][r].push(self.columns[vi][row].clone());
+        }
+
+        let mut cells : Vec<Series> = groups.iter().map(|column| column.iter().map(|g|
+            if g.is_empty() { DataTypes::null() } else { aggfunc.apply(g) }
+        ).collect()).collect();
+
+        if margins {
+            for (c, column) in groups.iter().enumerate() {
+                let all : Series = column.iter().flatten().cloned().collect();
+                cells[c].push(aggfunc.apply(&all));
+            }
+
+            let mut all_column : Series = (0..rows.len()).map(|r| {
+                let all : Series = groups.iter().flat_map(|column| column[r].iter()).cloned().collect();
+                aggfunc.apply(&all)
+            }).collect();
+            all_column.push(aggfunc.apply(&kept.iter().map(|&r| self.columns[vi][r].clone()).collect()));
+
+            rows.push(DataTypes::StringType(StringType{value : String::from("All")}));
+            labels.push(String::from("All"));
+            cells.push(all_column);
+        }
+
+        let mut result = DataFrame::new();
+        result.labels.push(index.to_string());
+        result.columns.push(rows);
+        result.labels.extend(labels);
+        result.columns.extend(cells);
+        Ok(result)
+    }
+
+    // reshapes wide data to long: `id_vars` are repeated for every
+    // column in `value_vars` (all remaining columns when empty), whose
+    // label goes to `var_name` and whose value goes to `value_name`
+    //
+    pub fn melt(&self, id_vars : Vec<&str>, value_vars : Vec<&str>, var_name : &str, value_name : &str) -> Result<DataFrame, DataFrameError> {
+        let ids : Vec<usize> = id_vars.iter().map(|&x| self.find_column_index(x)).collect::<Result<_, _>>()?;
+        let vars : Vec<usize> = if value_vars.is_empty() {
+            (0..self.labels.len()).filter(|ci| !ids.contains(ci)).collect()
+        } else {
+            value_vars.iter().map(|&x| self.find_column_index(x)).collect::<Result<_, _>>()?
+        };
+
+        let mut result = DataFrame::new();
+        for &ci in ids.iter() {
+            result.labels.push(self.labels[ci].to_string());
+            result.columns.push(vars.iter().flat_map(|_| self.columns[ci].iter().cloned()).collect());
+        }
+
+        result.labels.push(var_name.to_string());
+        result.columns.push(vars.iter().flat_map(|&vi|
+            std::iter::repeat_n(DataTypes::StringType(StringType{value : self.labels[vi].to_string()}), self.nrows())
+        ).collect());
+
+        result.labels.push(value_name.to_string());
+        result.columns.push(vars.iter().flat_map(|&vi| self.columns[vi].iter().cloned()).collect());
+
+        Ok(result)
+    }
+
+    pub fn group_by(&self, column_names : Vec<&str>) -> Group<'_> {
+        Group::new(self, column_names)
+    }
+
+    pub fn head(&self, num_rows : usize) {
+        let width = 15;
+        for lbl in self.labels.iter() { print!("{:^width$} ", lbl, width=width); }
+        println!();
+
+        for row in 0..num_rows {
+            print!("{}", row);
+            for column in 0..self.columns.len() {
+                match self.columns[column][row] {
+                    DataTypes::FloatType(FloatType{value : _}) => {
+                        print!(" {:^width$}", self.columns[column][row].fvalue(), width=width);
+                    },
+                    DataTypes::IntegerType(IntegerType{value : _}) => {
+                        print!(" {:^width$}", self.columns[column][row].ivalue(), width=width);
+                    },
+                    DataTypes::StringType(StringType{value : _}) | DataTypes::NullType(_) => { 
+                        print!(" {:^width$}", self.columns[column][row].svalue(), width=width);
+                    },
+                };
+            }
+            println!();
+        }
+    }
+    
+    pub fn println(&self) {
+        let col_count = self.labels.len();
+        for i in 0..col_count {
+            println!("{}", self.labels[i]);
+            let col_len = self.columns[i].len();
+            for j in 0..col_len {
+                self.columns[i][j].println();
+            }
+        }
+    }
+}
+
+pub struct BloomFilter {
+    size : usize,    
+    #[allow(dead_code)]
+    item_count : usize,
+    hash_count : usize,
+    #[allow(dead_code)]
+    false_probability : f64,
+    bit_array : Vec<bool>,
+}
+
+impl BloomFilter {
+
+    pub fn new(itemcount : usize, fprob : f64) -> BloomFilter {
+        let log2 = 2_f64.log(10.0);
+        let sz = (-(itemcount as f64 * fprob.log(10.0))/log2.powf(2.0)) as usize;
+        let hc = ((sz / itemcount) as f64 * log2) as usize;
+        BloomFilter{size : sz, item_count : itemcount, hash_count : hc, false_probability : fprob, bit_array : Vec::new() }
+    }
+
+    pub fn add<T>(&mut self, item : T)
+        where T : Hash
+    {
+        if self.bit_array.is_empty() {
+            self.bit_array.resize(self.size, false);
+        }
+
+        let mut s = DefaultHasher::new();
+        for i in 0..self.hash_count {
+            item.hash(&mut s);
+            let h = s.finish() as usize ^ i;
+            let digest = h % self.size;
+            self.bit_array[digest] = true;
+        }
+    }
+
+    pub fn contains<T>(& self, item : T) -> bool 
+        where T : Hash
+    {
+        if self.bit_array.is_empty() {
+            return false;
+        }
+
+        let mut s = DefaultHasher::new();
+        for i in 0..self.hash_count {
+            item.hash(&mut s);
+            let h = s.finish() as usize ^ i;
+            let digest = h % self.size;
+            if !self.bit_array[digest] {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+pub struct Group<'a> {
+    df : &'a DataFrame,    
+    column_indices : Vec<usize>,
+    indices : Vec< HashMap<DataTypes, Vec<usize>> >,
+}
+
+impl<'a> Group<'a> {
+
+    pub fn new(df : &'a DataFrame, column_names : Vec<&str>) -> Group<'a> {
+        let column_idxs : Vec<usize> = column_names.iter().map(|&x| df.get_column_index(x)).collect();
+
+        let mut grp_indices : Vec< HashMap<DataTypes, Vec<usize>> > = vec![ HashMap::new(); column_idxs.len()];
+        for (grp_idx, columns) in column_idxs.iter().enumerate().map(|(y,x)| (y, df.columns.get(*x)) ) {
+            for (row_idx, coval) in columns.unwrap().iter().enumerate() {
+                grp_indices.get_mut(grp_idx).unwrap().entry(coval.clone()).or_insert(Vec::new()).push(row_idx);
+            }
+        };
+
+        Group{df, column_indices : column_idxs, indices : grp_indices, }
+    }
+
+    pub fn fields(&self) -> Vec< String >{
+        self.column_indices.iter().map(|&x| self.df.labels[x].to_string()).collect()
+    }
+    
+    pub fn size(&self) -> Vec< (String, Vec<(String, usize)>) > {
+        self.column_indices.iter().zip(self.indices.iter()).map(
+            |(x, y)| (
+                String::from(self.df.labels.get(*x).unwrap()),
+                y.iter().map( |z| (z.0.svalue(), z.1.len()) ).collect()
+            )
+        ).collect()
+    }
+
+    pub fn mean(&self) -> Vec< Vec<DataTypes> > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::mean },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::mean },
+                DataTypes::StringType(StringType{value : _}) => { StringType::mean },
+                DataTypes::NullType(_) => { NullType::mean },
+            };
+
+            let data : Vec<Vec<DataTypes>> = self.indices[i].values().map(|y| y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()).collect();
+
+            data.iter().map(|x| op(x)).collect()
+
+        }).collect()
+    }
+
+    pub fn stddev(&self, ddof : usize) -> Vec< Vec<DataTypes> > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::stddev },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::stddev },
+                DataTypes::StringType(StringType{value : _}) => { StringType::stddev },
+                DataTypes::NullType(_) => { NullType::stddev },
+            };
+
+            let data : Vec<Vec<DataTypes>> = self.indices[i].values().map(|y| y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()).collect();
+
+            data.iter().map(|x| op(x, ddof)).collect()
+
+        }).collect()
+    }
+
+    pub fn pstddev(&self) -> Vec< Vec<DataTypes> > {
+        self.stddev(0)
+    }
+
+    pub fn variance(&self, ddof : usize) -> Vec< Vec<DataTypes> > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::variance },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::variance },
+                DataTypes::StringType(StringType{value : _}) => { StringType::variance },
+                DataTypes::NullType(_) => { NullType::variance },
+            };
+
+            let data : Vec<Vec<DataTypes>> = self.indices[i].values().map(|y| y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()).collect();
+
+            data.iter().map(|x| op(x, ddof)).collect()
+
+        }).collect()
+    }
+    
+    fn group_data(&self, i : usize, ci : usize) -> Vec< Vec<DataTypes> > {
+        self.indices[i].values().map( |y|
+            y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()
+        ).collect()
+    }
+
+    pub fn sum(&self) -> Vec< Vec<DataTypes> > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::sum },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::sum },
+                DataTypes::StringType(StringType{value : _}) => { StringType::sum },
+                DataTypes::NullType(_) => { NullType::sum },
+            };
+
+            self.group_data(i, ci).iter().map(|x| op(x)).collect()
+        }).collect()
+    }
+
+    pub fn min(&self) -> Vec< Vec<DataTypes> > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::min },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::min },
+                DataTypes::StringType(StringType{value : _}) => { StringType::min },
+                DataTypes::NullType(_) => { NullType::min },
+            };
+
+            self.group_data(i, ci).iter().map(|x| op(x)).collect()
+        }).collect()
+    }
+
+    pub fn max(&self) -> Vec< Vec<DataTypes> > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::max },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::max },
+                DataTypes::StringType(StringType{value : _}) => { StringType::max },
+                DataTypes::NullType(_) => { NullType::max },
+            };
+
+            self.group_data(i, ci).iter().map(|x| op(x)).collect()
+        }).collect()
+    }
+
+    // row of the frame holding each group's minimum
+    //
+    pub fn argmin(&self) -> Vec< Vec< Option<usize> > > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::argmin },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::argmin },
+                DataTypes::StringType(StringType{value : _}) => { StringType::argmin },
+                DataTypes::NullType(_) => { NullType::argmin },
+            };
+
+            self.indices[i].values().zip(self.group_data(i, ci).iter()).map(|(rows, x)| op(x).map(|p| rows[p])).collect()
+        }).collect()
+    }
+
+    // row of the frame holding each group's maximum
+    //
+    pub fn argmax(&self) -> Vec< Vec< Option<usize> > > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::argmax },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::argmax },
+                DataTypes::StringType(StringType{value : _}) => { StringType::argmax },
+                DataTypes::NullType(_) => { NullType::argmax },
+            };
+
+            self.indices[i].values().zip(self.group_data(i, ci).iter()).map(|(rows, x)| op(x).map(|p| rows[p])).collect()
+        }).collect()
+    }
+
+    pub fn median(&self) -> Vec< Vec<DataTypes> > {
+        self.group_quantile(0.5, QuantileInterpolation::Linear)
+    }
+
+    pub fn quantile(&self, q : f64, interpolation : QuantileInterpolation) -> Result< Vec< Vec<DataTypes> >, DataFrameError> {
+        check_quantile(q)?;
+        Ok(self.group_quantile(q, interpolation))
+    }
+
+    fn group_quantile(&self, q : f64, interpolation : QuantileInterpolation) -> Vec< Vec<DataTypes> > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::quantile },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::quantile },
+                DataTypes::StringType(StringType{value : _}) => { StringType::quantile },
+                DataTypes::NullType(_) => { NullType::quantile },
+            };
+
+            self.group_data(i, ci).iter().map(|x| op(x, q, interpolation)).collect()
+        }).collect()
+    }
+
+    pub fn mode(&self) -> Vec< Vec< Vec<DataTypes> > > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::mode },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::mode },
+                DataTypes::StringType(StringType{value : _}) => { StringType::mode },
+                DataTypes::NullType(_) => { NullType::mode },
+            };
+
+            self.group_data(i, ci).iter().map(|x| op(x)).collect()
+        }).collect()
+    }
+
+    pub fn skewness(&self, unbiased : bool) -> Vec< Vec<DataTypes> > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::skewness },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::skewness },
+                DataTypes::StringType(StringType{value : _}) => { StringType::skewness },
+                DataTypes::NullType(_) => { NullType::skewness },
+            };
+
+            self.group_data(i, ci).iter().map(|x| op(x, unbiased)).collect()
+        }).collect()
+    }
+
+    pub fn kurtosis(&self, unbiased : bool) -> Vec< Vec<DataTypes> > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::kurtosis },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::kurtosis },
+                DataTypes::StringType(StringType{value : _}) => { StringType::kurtosis },
+                DataTypes::NullType(_) => { NullType::kurtosis },
+            };
+
+            self.group_data(i, ci).iter().map(|x| op(x, unbiased)).collect()
+        }).collect()
+    }
+
+    pub fn sem(&self) -> Vec< Vec<DataTypes> > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::sem },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::sem },
+                DataTypes::StringType(StringType{value : _}) => { StringType::sem },
+                DataTypes::NullType(_) => { NullType::sem },
+            };
+
+            self.group_data(i, ci).iter().map(|x| op(x)).collect()
+        }).collect()
+    }
+
+    pub fn cv(&self) -> Vec< Vec<DataTypes> > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::cv },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::cv },
+                DataTypes::StringType(StringType{value : _}) => { StringType::cv },
+                DataTypes::NullType(_) => { NullType::cv },
+            };
+
+            self.group_data(i, ci).iter().map(|x| op(x)).collect()
+        }).collect()
+    }
+
+    pub fn simple_rolling_mean(&self, window : usize) -> Vec< Vec< Vec<DataTypes> > > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::simple_rolling_mean },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::simple_rolling_mean },
+                DataTypes::StringType(StringType{value : _}) => { StringType::simple_rolling_mean },
+                DataTypes::NullType(_) => { NullType::simple_rolling_mean },
+            };
+
+            let data : Vec<Vec<DataTypes>> = self.indices[i].values().map(|y| y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()).collect();
+
+            data.iter().map(|x| op(x, window)).collect()        
+        }).collect()
+    }
+
+    pub fn rolling_stddev(&self, window : usize, ddof : usize) -> Vec< Vec< Vec<DataTypes> > > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::rolling_stddev },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::rolling_stddev },
+                DataTypes::StringType(StringType{value : _}) => { StringType::rolling_stddev },
+                DataTypes::NullType(_) => { NullType::rolling_stddev },
+            };
+
+            let data : Vec<Vec<DataTypes>> = self.indices[i].values().map(|y| y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()).collect();
+
+            data.iter().map(|x| op(x, window, ddof)).collect()        
+        }).collect()
+    }
+    
+    pub fn rolling_variance(&self, window : usize, ddof : usize) -> Vec< Vec< Vec<DataTypes> > > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::rolling_variance },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::rolling_variance },
+                DataTypes::StringType(StringType{value : _}) => { StringType::rolling_variance },
+                DataTypes::NullType(_) => { NullType::rolling_variance },
+            };
+
+            let data : Vec<Vec<DataTypes>> = self.indices[i].values().map(|y| y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()).collect();
+
+            data.iter().map(|x| op(x, window, ddof)).collect()        
+        }).collect()
+    }
+
+    pub fn diff(&self) -> Vec< Vec< Vec<DataTypes> > > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::diff },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::diff },
+                DataTypes::StringType(StringType{value : _}) => { StringType::diff },
+                DataTypes::NullType(_) => { NullType::diff },
+            };
+
+            let data : Vec< Vec<DataTypes> > = self.indices[i].values().map(|y| y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()).collect();
+
+            data.iter().map(|x| op(x)).collect()
+        }).collect()
+    }
+    
+    pub fn rank(&self, method : RankMethod, ascending : bool, pct : bool, nulls : NullRank) -> Vec< Vec< Vec<DataTypes> > > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+            self.group_data(i, ci).iter().map(|x| rank_series(x, method, ascending, pct, nulls)).collect()
+        }).collect()
+    }
+
+    // bootstrap intervals of `column_name` per group, see
+    // `DataFrame::bootstrap`; one list per grouping column of
+    // (group key, interval) sorted by key. each group's generator is
+    // seeded by mixing `seed` with the group's position in that order
+    //
+    pub fn bootstrap(&self, column_name : &str, aggfunc : Aggregation, method : BootstrapMethod, confidence : f64, resamples : usize, seed : u64) -> Result< Vec< Vec<(DataTypes, ConfidenceInterval)> >, DataFrameError> {
+        check_bootstrap(confidence, resamples)?;
+        aggfunc.check()?;
+        let idx = self.df.find_column_index(column_name)?;
+        let column = &self.df.columns[idx];
+
+        Ok(self.indices.iter().map(|groups| {
+            let mut keys : Vec<&DataTypes> = groups.keys().collect();
+            keys.sort();
+
+            keys.into_iter().enumerate().map(|(position, key)| {
+                let values : Series = groups[key].iter().map(|&r| &column[r]).filter(|v| !v.is_null()).cloned().collect();
+                let mut rng = SplitMix64::new(SplitMix64::new(seed.wrapping_add(position as u64)).next_u64());
+                (key.clone(), bootstrap_interval(&values, aggfunc, method, confidence, resamples, &mut rng))
+            }).collect()
+        }).collect())
+    }
+
+    // `DataFrame::interpolate` within each group, one frame aligned
+    // with the rows of the frame per grouping column
+    //
+    pub fn interpolate(&self, column_name : &str, method : InterpolationMethod, time_column : Option<&str>, limit : Option<usize>, direction : LimitDirection) -> Result< Vec<DataFrame>, DataFrameError> {
+        let idx = self.df.find_column_index(column_name)?;
+        self.df.numeric_values(idx)?;
+        let times = match time_column {
+            Some(name) => {
+                let tidx = self.df.find_column_index(name)?;
+                Some(self.df.columns[tidx].iter().map(|t| t.key_value()).collect::<Vec<Option<f64>>>())
+            },
+            None if method == InterpolationMethod::Time => {
+                return Err(DataFrameError::InvalidArgument(String::from("time interpolation needs a time column")));
+            },
+            None => None,
         };
 
-        op(&self.columns[idx], &column_name.to_string())
+        self.indices.iter().map(|groups| {
+            let mut filled = self.df.columns[idx].clone();
+            for rows in groups.values() {
+                let gx : Vec<f64> = match (&times, method) {
+                    (Some(times), m) if m != InterpolationMethod::Linear => {
+                        let gx : Vec<f64> = rows.iter().map(|&r| times[r]).collect::<Option<_>>().ok_or_else(||
+                            DataFrameError::InvalidArgument(format!("column '{}' has a value that is not a number or date", time_column.unwrap_or_default())))?;
+                        if gx.windows(2).any(|w| w[0] >= w[1]) {
+                            return Err(DataFrameError::NotSorted(time_column.unwrap_or_default().to_string()));
+                        }
+                        gx
+                    },
+                    _ => (0..rows.len()).map(|i| i as f64).collect(),
+                };
+                let gy : Series = rows.iter().map(|&r| self.df.columns[idx][r].clone()).collect();
+
+                for (&r, v) in rows.iter().zip(interpolate_series(&gx, &gy, method, limit, direction)) {
+                    filled[r] = v;
+                }
+            }
+            Ok(DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![filled,] })
+        }).collect()
     }
 
-    pub fn series(&self, column_name : &str) -> Series {
-        let idx = self.get_column_index(column_name);
-        self.columns[idx].clone()
+    pub fn pct_change(&self) -> Vec< Vec< Vec<DataTypes> > > {
+        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+
+            let op = match self.df.column_type(ci) {
+                DataTypes::FloatType(FloatType{value : _}) => { FloatType::pct_change },
+                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::pct_change },
+                DataTypes::StringType(StringType{value : _}) => { StringType::pct_change },
+                DataTypes::NullType(_) => { NullType::pct_change },
+            };
+
+            let data : Vec<Vec<DataTypes>> = self.indices[i].values().map(|y| y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()).collect();
+
+            data.iter().map(|x| op(x)).collect()
+
+        }).collect()
     }
 
-    pub fn mean(&self, column_name : &str) -> DataTypes {
-        let idx = self.get_column_index(column_name);
+    pub fn print(&self) {
+        for (i, k) in self.column_indices.iter().enumerate().map(|(y, x)| (y, self.df.labels.get(*x).unwrap())) {
+            println!("{}", k);
+            for (val, rows) in self.indices.get(i).unwrap().iter() {
+                let k_str = val.svalue();
+                print!("{}\t", k_str);
+                for r in rows {
+                    print!("{} ", r);
+                }
+            }
+            println!();
+        }
+    }
+}
 
-        let op = match self.columns[idx].first().unwrap() {
-            DataTypes::FloatType(FloatType{value : _}) => { FloatType::mean },
-            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::mean },
-            DataTypes::StringType(StringType{value : _}) => { StringType::mean },
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
 
-        op(&self.columns[idx])
+    // sorting
+
+    #[test]
+    fn ordering_puts_numbers_before_strings_and_nulls_last() {
+        let mut values = [null(), s("b"), f(f64::NAN), i(2), f(1.5), s("a"), f(2.0)];
+        values.sort();
+        let rendered : Vec<String> = values.iter().map(|v| v.svalue()).collect();
+        assert_eq!(rendered, vec!["1.5", "2", "2", "NaN", "a", "b", "null"]);
+        assert!(matches!(values[1], DataTypes::IntegerType(_)));
     }
 
-    pub fn stddev(&self, column_name : &str) -> DataTypes {
-        let idx = self.get_column_index(column_name);
+    #[test]
+    fn sort_by_several_columns_is_stable() {
+        let df = frame(vec![("k", strs(&["b", "a", "b", "a"])), ("v", ints(&[1, 2, 3, 2])), ("row", ints(&[0, 1, 2, 3]))]);
 
-        let op = match self.columns[idx].first().unwrap() {
-            DataTypes::FloatType(FloatType{value : _}) => { FloatType::stddev },
-            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::stddev },
-            DataTypes::StringType(StringType{value : _}) => { StringType::stddev },
-        };
+        let sorted = df.sort_by(vec!["k", "v"], vec![true, false], false).unwrap();
+        assert_eq!(text(&sorted, "row"), vec!["1", "3", "2", "0"]);
 
-        op(&self.columns[idx])
+        let sorted = df.sort_by(vec!["k"], vec![false], false).unwrap();
+        assert_eq!(text(&sorted, "row"), vec!["0", "2", "1", "3"]);
     }
 
-    pub fn pstddev(&self, column_name : &str) -> DataTypes {
-        let idx = self.get_column_index(column_name);
+    #[test]
+    fn argsort_places_nulls_independent_of_direction() {
+        let df = frame(vec![("v", vec![f(2.0), null(), f(1.0), f(f64::NAN)])]);
+        assert_eq!(df.argsort(vec!["v"], vec![true], false).unwrap(), vec![2, 0, 1, 3]);
+        assert_eq!(df.argsort(vec!["v"], vec![false], false).unwrap(), vec![0, 2, 1, 3]);
+        assert_eq!(df.argsort(vec!["v"], vec![false], true).unwrap(), vec![1, 3, 0, 2]);
+    }
 
-        let op = match self.columns[idx].first().unwrap() {
-            DataTypes::FloatType(FloatType{value : _}) => { FloatType::pstddev },
-            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::pstddev },
-            DataTypes::StringType(StringType{value : _}) => { StringType::pstddev },
-        };
+    #[test]
+    fn argsort_reports_bad_arguments() {
+        let df = frame(vec![("a", ints(&[1])), ("b", ints(&[2]))]);
+        assert_eq!(df.argsort(vec!["c"], vec![true], false), Err(DataFrameError::ColumnNotFound(String::from("c"))));
+        assert!(matches!(df.argsort(vec!["a", "b"], vec![true, false, true], false), Err(DataFrameError::InvalidArgument(_))));
+        assert_eq!(DataFrame::new().argsort(vec![], vec![true], false).unwrap(), Vec::<usize>::new());
+    }
 
-        op(&self.columns[idx])
+    #[test]
+    fn nlargest_and_nsmallest_skip_nulls_and_keep_tie_order() {
+        let df = frame(vec![("v", vec![i(3), null(), i(5), i(3), i(1)]), ("row", ints(&[0, 1, 2, 3, 4]))]);
+        assert_eq!(text(&df.nlargest(3, vec!["v"]).unwrap(), "row"), vec!["2", "0", "3"]);
+        assert_eq!(text(&df.nsmallest(2, vec!["v"]).unwrap(), "row"), vec!["4", "0"]);
+        assert_eq!(df.nsmallest(10, vec!["v"]).unwrap().nrows(), 4);
+        assert!(df.nlargest(1, vec!["w"]).is_err());
     }
 
-    pub fn variance(&self, column_name : &str) -> DataTypes {
-        let idx = self.get_column_index(column_name);
+    // joins
 
-        let op = match self.columns[idx].first().unwrap() {
-            DataTypes::FloatType(FloatType{value : _}) => { FloatType::variance },
-            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::variance },
-            DataTypes::StringType(StringType{value : _}) => { StringType::variance },
-        };
+    fn join_frames() -> (DataFrame, DataFrame) {
+        let left = frame(vec![("k", ints(&[1, 2, 3])), ("v", strs(&["a", "b", "c"]))]);
+        let right = frame(vec![("k", ints(&[2, 3, 3, 4])), ("v", strs(&["x", "y", "z", "w"]))]);
+        (left, right)
+    }
 
-        op(&self.columns[idx])
+    #[test]
+    fn join_types_pick_the_expected_rows() {
+        let (left, right) = join_frames();
+        let join = |how| left.join(&right, vec!["k"], how, ("_l", "_r"), JoinValidate::ManyToMany).unwrap();
+
+        let inner = join(JoinType::Inner);
+        assert_eq!(inner.labels, vec!["k", "v_l", "v_r"]);
+        assert_eq!(text(&inner, "k"), vec!["2", "3", "3"]);
+        assert_eq!(text(&inner, "v_r"), vec!["x", "y", "z"]);
+
+        let left_join = join(JoinType::Left);
+        assert_eq!(text(&left_join, "k"), vec!["1", "2", "3", "3"]);
+        assert_eq!(text(&left_join, "v_r"), vec!["null", "x", "y", "z"]);
+
+        let right_join = join(JoinType::Right);
+        assert_eq!(text(&right_join, "k"), vec!["2", "3", "3", "4"]);
+        assert_eq!(text(&right_join, "v_l"), vec!["b", "c", "c", "null"]);
+
+        let outer = join(JoinType::Outer);
+        assert_eq!(text(&outer, "k"), vec!["1", "2", "3", "3", "4"]);
+        assert_eq!(text(&outer, "v_l"), vec!["a", "b", "c", "c", "null"]);
+
+        assert_eq!(text(&join(JoinType::Semi), "v"), vec!["b", "c"]);
+        assert_eq!(text(&join(JoinType::Anti), "v"), vec!["a"]);
+
+        let cross = join(JoinType::Cross);
+        assert_eq!(cross.shape(), (12, 4));
+        assert_eq!(cross.labels, vec!["k_l", "v_l", "k_r", "v_r"]);
     }
 
-    pub fn simple_rolling_mean(&self, column_name : &str, window : usize) -> DataFrame {
-        let idx : usize = self.get_column_index(column_name);
+    #[test]
+    fn join_validation_checks_key_uniqueness() {
+        let (left, right) = join_frames();
+        assert!(left.join(&right, vec!["k"], JoinType::Inner, ("_l", "_r"), JoinValidate::OneToMany).is_ok());
+        assert!(matches!(left.join(&right, vec!["k"], JoinType::Inner, ("_l", "_r"), JoinValidate::OneToOne), Err(DataFrameError::MergeValidation(_))));
+        assert!(matches!(right.join(&left, vec!["k"], JoinType::Inner, ("_l", "_r"), JoinValidate::OneToMany), Err(DataFrameError::MergeValidation(_))));
+        assert!(matches!(left.join(&right, vec![], JoinType::Cross, ("_l", "_r"), JoinValidate::OneToOne), Err(DataFrameError::InvalidArgument(_))));
+        assert_eq!(error(left.join(&right, vec!["x"], JoinType::Inner, ("_l", "_r"), JoinValidate::ManyToMany)), DataFrameError::ColumnNotFound(String::from("x")));
+    }
 
-        let op = match self.columns[idx].first().unwrap() {
-            DataTypes::FloatType(FloatType{value : _}) => { FloatType::simple_rolling_mean },
-            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::simple_rolling_mean },
-            DataTypes::StringType(StringType{value : _}) => { StringType::simple_rolling_mean },
+    #[test]
+    fn join_with_empty_frames() {
+        let (left, _) = join_frames();
+        let empty = frame(vec![("k", Series::new()), ("w", Series::new())]);
+        assert_eq!(left.join(&empty, vec!["k"], JoinType::Inner, ("_l", "_r"), JoinValidate::OneToOne).unwrap().shape(), (0, 3));
+        assert_eq!(text(&left.join(&empty, vec!["k"], JoinType::Left, ("_l", "_r"), JoinValidate::OneToOne).unwrap(), "w"), vec!["null", "null", "null"]);
+    }
+
+
+    // as-of joins
+
+    #[test]
+    fn timestamps_count_seconds_since_the_epoch() {
+        assert_eq!(s("1970-01-01").timestamp(), Some(0));
+        assert_eq!(s("20000301").timestamp(), Some(951868800));
+        assert_eq!(s("2000-02-29T23:59:59").timestamp(), Some(951868799));
+        assert_eq!(s("2000-13-01").timestamp(), None);
+        assert_eq!(i(5).timestamp(), None);
+    }
+
+    #[test]
+    fn join_asof_directions_and_tolerance() {
+        let left = frame(vec![("t", ints(&[1, 5, 10])), ("a", strs(&["p", "q", "r"]))]);
+        let right = frame(vec![("t", ints(&[2, 3, 7])), ("b", strs(&["x", "y", "z"]))]);
+        let asof = |direction, tolerance| {
+            let joined = left.join_asof(&right, "t", vec![], direction, tolerance, ("_l", "_r")).unwrap();
+            text(&joined, "b")
         };
 
-        DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![op(&self.columns[idx], window),] }
+        assert_eq!(asof(AsofDirection::Backward, None), vec!["null", "y", "z"]);
+        assert_eq!(asof(AsofDirection::Forward, None), vec!["x", "z", "null"]);
+        assert_eq!(asof(AsofDirection::Nearest, None), vec!["x", "y", "z"]);
+        assert_eq!(asof(AsofDirection::Backward, Some(2.0)), vec!["null", "y", "null"]);
+    }
+
+    #[test]
+    fn join_asof_matches_within_by_partitions() {
+        let left = frame(vec![("t", ints(&[1, 2])), ("g", strs(&["a", "b"]))]);
+        let right = frame(vec![("t", ints(&[0, 1])), ("g", strs(&["b", "a"])), ("v", ints(&[10, 20]))]);
+        let joined = left.join_asof(&right, "t", vec!["g"], AsofDirection::Backward, None, ("_l", "_r")).unwrap();
+        assert_eq!(joined.labels, vec!["t", "g", "v"]);
+        assert_eq!(text(&joined, "v"), vec!["20", "10"]);
+    }
+
+    #[test]
+    fn join_asof_orders_datetime_keys_by_time() {
+        // "9:00" sorts after "10:00" as text but before it as a time
+        let left = frame(vec![("t", strs(&["2020-01-01 9:30", "2020-01-01 10:30"]))]);
+        let right = frame(vec![("t", strs(&["2020-01-01 9:00", "2020-01-01 10:00"])), ("v", ints(&[1, 2]))]);
+        let joined = left.join_asof(&right, "t", vec![], AsofDirection::Backward, Some(1800.0), ("_l", "_r")).unwrap();
+        assert_eq!(text(&joined, "v"), vec!["1", "2"]);
+
+        let unsorted = frame(vec![("t", ints(&[2, 1]))]);
+        assert_eq!(error(unsorted.join_asof(&right, "t", vec![], AsofDirection::Backward, None, ("_l", "_r"))), DataFrameError::NotSorted(String::from("t")));
+
+        let words = frame(vec![("t", strs(&["soon"]))]);
+        assert!(matches!(words.join_asof(&right, "t", vec![], AsofDirection::Backward, None, ("_l", "_r")), Err(DataFrameError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn join_asof_leaves_null_keys_unmatched() {
+        let left = frame(vec![("t", vec![null(), f(3.0)])]);
+        let right = frame(vec![("t", floats(&[1.0])), ("v", ints(&[7]))]);
+        let joined = left.join_asof(&right, "t", vec![], AsofDirection::Nearest, None, ("_l", "_r")).unwrap();
+        assert_eq!(text(&joined, "v"), vec!["null", "7"]);
+    }
+
+
+    // concatenation
+
+    #[test]
+    fn concat_rows_aligns_columns_and_records_the_source() {
+        let a = frame(vec![("x", ints(&[1, 2])), ("y", strs(&["a", "b"]))]);
+        let b = frame(vec![("y", strs(&["c"])), ("z", floats(&[0.5]))]);
+        let stacked = DataFrame::concat_rows(&[&a, &b], Some("frame")).unwrap();
+
+        assert_eq!(stacked.labels, vec!["x", "y", "z", "frame"]);
+        assert_eq!(text(&stacked, "x"), vec!["1", "2", "null"]);
+        assert_eq!(text(&stacked, "y"), vec!["a", "b", "c"]);
+        assert_eq!(text(&stacked, "z"), vec!["null", "null", "0.5"]);
+        assert_eq!(text(&stacked, "frame"), vec!["0", "0", "1"]);
+
+        assert_eq!(DataFrame::concat_rows(&[], None).unwrap().shape(), (0, 0));
+        assert!(matches!(DataFrame::concat_rows(&[&a, &b], Some("z")), Err(DataFrameError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn concat_columns_pads_short_frames_and_rejects_repeated_labels() {
+        let a = frame(vec![("x", ints(&[1, 2, 3]))]);
+        let b = frame(vec![("y", ints(&[4]))]);
+        let wide = DataFrame::concat_columns(&[&a, &b]).unwrap();
+        assert_eq!(wide.shape(), (3, 2));
+        assert_eq!(text(&wide, "y"), vec!["4", "null", "null"]);
+
+        assert!(matches!(DataFrame::concat_columns(&[&a, &a]), Err(DataFrameError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn append_extends_in_place() {
+        let mut a = frame(vec![("x", ints(&[1]))]);
+        a.append(&frame(vec![("y", ints(&[2, 3]))]));
+        assert_eq!(a.shape(), (3, 2));
+        assert_eq!(text(&a, "x"), vec!["1", "null", "null"]);
+        assert_eq!(text(&a, "y"), vec!["null", "2", "3"]);
+
+        a.append(&DataFrame::new());
+        assert_eq!(a.shape(), (3, 2));
+    }
+
+
+    // reshaping
+
+    fn sales() -> DataFrame {
+        frame(vec![
+            ("day", strs(&["mon", "mon", "tue", "tue", "tue"])),
+            ("shop", strs(&["a", "b", "a", "a", "b"])),
+            ("units", ints(&[1, 2, 3, 5, 7])),
+        ])
+    }
+
+    #[test]
+    fn pivot_spreads_values_and_rejects_repeated_cells() {
+        let df = frame(vec![("day", strs(&["tue", "mon", "mon"])), ("shop", strs(&["a", "a", "b"])), ("units", ints(&[3, 1, 2]))]);
+        let wide = df.pivot("day", "shop", "units").unwrap();
+        assert_eq!(wide.labels, vec!["day", "a", "b"]);
+        assert_eq!(text(&wide, "day"), vec!["mon", "tue"]);
+        assert_eq!(text(&wide, "a"), vec!["1", "3"]);
+        assert_eq!(text(&wide, "b"), vec!["2", "null"]);
+
+        assert!(matches!(sales().pivot("day", "shop", "units"), Err(DataFrameError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn pivot_table_aggregates_cells_and_margins() {
+        let table = sales().pivot_table("day", "shop", "units", Aggregation::Sum, true).unwrap();
+        assert_eq!(table.labels, vec!["day", "a", "b", "All"]);
+        assert_eq!(text(&table, "day"), vec!["mon", "tue", "All"]);
+        assert_eq!(text(&table, "a"), vec!["1", "8", "9"]);
+        assert_eq!(text(&table, "b"), vec!["2", "7", "9"]);
+        assert_eq!(text(&table, "All"), vec!["3", "15", "18"]);
+
+        let means = sales().pivot_table("day", "shop", "units", Aggregation::Mean, false).unwrap();
+        assert_eq!(text(&means, "a"), vec!["1", "4"]);
+
+        assert!(matches!(sales().pivot_table("day", "shop", "units", Aggregation::Quantile(1.5, QuantileInterpolation::Linear), false), Err(DataFrameError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn pivots_drop_null_keys() {
+        let df = frame(vec![("day", vec![s("mon"), null(), s("tue"), s("mon")]), ("shop", vec![s("a"), s("a"), s("b"), null()]), ("units", ints(&[1, 2, 3, 4]))]);
+        let wide = df.pivot("day", "shop", "units").unwrap();
+        assert_eq!(wide.labels, vec!["day", "a", "b"]);
+        assert_eq!(text(&wide, "day"), vec!["mon", "tue"]);
+
+        let table = df.pivot_table("day", "shop", "units", Aggregation::Sum, true).unwrap();
+        assert_eq!(text(&table, "All"), vec!["1", "3", "4"]);
+    }
+
+    #[test]
+    fn pivots_reject_colliding_column_labels() {
+        let mixed = frame(vec![("day", strs(&["mon", "mon"])), ("shop", vec![i(1), f(1.0)]), ("units", ints(&[1, 2]))]);
+        assert!(matches!(mixed.pivot("day", "shop", "units"), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(mixed.pivot_table("day", "shop", "units", Aggregation::Sum, false), Err(DataFrameError::InvalidArgument(_))));
+
+        let index = frame(vec![("day", strs(&["mon", "tue"])), ("shop", strs(&["day", "a"])), ("units", ints(&[1, 2]))]);
+        assert!(matches!(index.pivot("day", "shop", "units"), Err(DataFrameError::InvalidArgument(_))));
+
+        let all = frame(vec![("day", strs(&["mon", "tue"])), ("shop", strs(&["All", "a"])), ("units", ints(&[1, 2]))]);
+        assert!(all.pivot_table("day", "shop", "units", Aggregation::Sum, false).is_ok());
+        assert!(matches!(all.pivot_table("day", "shop", "units", Aggregation::Sum, true), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(all.pivot_table("shop", "day", "units", Aggregation::Sum, true), Err(DataFrameError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn melt_stacks_value_columns() {
+        let df = frame(vec![("id", ints(&[1, 2])), ("x", ints(&[10, 20])), ("y", ints(&[30, 40]))]);
+        let long = df.melt(vec!["id"], vec![], "variable", "value").unwrap();
+        assert_eq!(long.labels, vec!["id", "variable", "value"]);
+        assert_eq!(text(&long, "id"), vec!["1", "2", "1", "2"]);
+        assert_eq!(text(&long, "variable"), vec!["x", "x", "y", "y"]);
+        assert_eq!(text(&long, "value"), vec!["10", "20", "30", "40"]);
+
+        assert_eq!(df.melt(vec!["id"], vec!["y"], "variable", "value").unwrap().nrows(), 2);
+        assert!(df.melt(vec!["nope"], vec![], "variable", "value").is_err());
+    }
+
+
+    // frequency tables
+
+    #[test]
+    fn value_counts_counts_non_null_values() {
+        let df = frame(vec![("v", vec![s("b"), s("a"), null(), s("b"), s("c")])]);
+
+        let counts = df.value_counts("v", false, false, None).unwrap();
+        assert_eq!(text(&counts, "v"), vec!["a", "b", "c"]);
+        assert_eq!(text(&counts, "count"), vec!["1", "2", "1"]);
+
+        let sorted = df.value_counts("v", true, true, None).unwrap();
+        assert_eq!(text(&sorted, "v"), vec!["b", "a", "c"]);
+        assert_eq!(values(&sorted, "proportion"), vec![0.5, 0.25, 0.25]);
+
+        let empty = frame(vec![("v", vec![null(), null()])]);
+        assert_eq!(empty.value_counts("v", false, false, None).unwrap().nrows(), 0);
+    }
+
+    #[test]
+    fn value_counts_bins_match_pandas() {
+        // pd.Series([1, 2, 3, 4]).value_counts(bins=3, sort=False)
+        let df = frame(vec![("v", ints(&[1, 2, 3, 4]))]);
+        let counts = df.value_counts("v", false, false, Some(3)).unwrap();
+        assert_eq!(text(&counts, "v"), vec!["(0.997, 2]", "(2, 3]", "(3, 4]"]);
+        assert_eq!(text(&counts, "count"), vec!["2", "1", "1"]);
+
+        assert!(df.value_counts("v", false, false, Some(0)).is_err());
+        assert!(frame(vec![("v", strs(&["a"]))]).value_counts("v", false, false, Some(2)).is_err());
+    }
+
+    #[test]
+    fn crosstab_counts_with_margins_and_normalization() {
+        let df = frame(vec![
+            ("sex", vec![s("f"), s("m"), s("f"), s("m"), s("f"), null()]),
+            ("smoker", strs(&["y", "n", "n", "n", "y", "y"])),
+        ]);
+
+        let table = df.crosstab(vec!["sex"], vec!["smoker"], true, Normalize::Off).unwrap();
+        assert_eq!(table.labels, vec!["sex", "n", "y", "All"]);
+        assert_eq!(text(&table, "sex"), vec!["f", "m", "All"]);
+        assert_eq!(text(&table, "n"), vec!["1", "2", "3"]);
+        assert_eq!(text(&table, "y"), vec!["2", "0", "2"]);
+        assert_eq!(text(&table, "All"), vec!["3", "2", "5"]);
+
+        let rows = df.crosstab(vec!["sex"], vec!["smoker"], false, Normalize::Rows).unwrap();
+        all_close(&values(&rows, "y"), &[2.0 / 3.0, 0.0], 1e-12);
+        let columns = df.crosstab(vec!["sex"], vec!["smoker"], false, Normalize::Columns).unwrap();
+        all_close(&values(&columns, "n"), &[1.0 / 3.0, 2.0 / 3.0], 1e-12);
+        let all = df.crosstab(vec!["sex"], vec!["smoker"], false, Normalize::All).unwrap();
+        all_close(&values(&all, "n"), &[0.2, 0.4], 1e-12);
+    }
+
+
+    // describe
+
+    #[test]
+    fn describe_matches_pandas() {
+        let df = frame(vec![("x", vec![i(1), i(2), null(), i(3), i(4)]), ("c", vec![s("b"), s("a"), s("b"), s("a"), null()])]);
+        let summary = df.describe();
+
+        assert_eq!(summary.labels, vec!["statistic", "x", "c"]);
+        assert_eq!(text(&summary, "statistic"), vec!["count", "null", "mean", "std", "min", "25%", "50%", "75%", "max", "unique", "top", "freq"]);
+
+        let x = values(&summary, "x");
+        assert_eq!(&x[..2], &[4.0, 1.0]);
+        all_close(&x[2..9], &[2.5, 1.2909944487358056, 1.0, 1.75, 2.5, 3.25, 4.0], 1e-12);
+        assert_eq!(text(&summary, "x")[9..], ["null", "null", "null"]);
+
+        // ties for the most frequent value go to the smallest
+        assert_eq!(text(&summary, "c"), vec!["4", "1", "null", "null", "null", "null", "null", "null", "null", "2", "a", "2"]);
+    }
+
+    #[test]
+    fn describe_keeps_its_label_clear_of_columns() {
+        let df = frame(vec![("statistic", floats(&[1.0])), ("_statistic", floats(&[2.0]))]);
+        assert_eq!(df.describe().labels, vec!["__statistic", "statistic", "_statistic"]);
+    }
+
+    #[test]
+    fn describe_all_null_and_empty_columns() {
+        let df = frame(vec![("x", vec![f(f64::NAN), null()])]);
+        let x = values(&df.describe(), "x");
+        assert_eq!(&x[..2], &[0.0, 2.0]);
+        assert!(x[2..].iter().all(|v| v.is_nan()));
+
+        assert_eq!(DataFrame::new().describe().shape(), (2, 1));
+    }
+
+    #[test]
+    fn describe_skips_string_cells_of_a_numeric_column() {
+        // load_csv keeps a blank field as an empty string
+        let df = frame(vec![("x", vec![f(1.0), s(""), f(3.0), f(2.0)])]);
+        let x = values(&df.describe(), "x");
+        assert_eq!(&x[..2], &[4.0, 0.0]);
+        all_close(&x[2..], &[2.0, 1.0, 1.0, 1.5, 2.0, 2.5, 3.0], 1e-12);
+    }
+
+
+    // order statistics
+
+    #[test]
+    fn min_max_and_their_positions_skip_nulls() {
+        let df = frame(vec![("x", vec![null(), f(3.0), f(-1.0), f(f64::NAN), f(-1.0), f(7.0)]), ("c", strs(&["b", "a", "c", "a", "b", "c"]))]);
+        assert_eq!(df.min("x").unwrap().fvalue(), -1.0);
+        assert_eq!(df.max("x").unwrap().fvalue(), 7.0);
+        assert_eq!(df.argmin("x").unwrap(), Some(2));
+        assert_eq!(df.argmax("x").unwrap(), Some(5));
+        assert_eq!(df.min("c").unwrap().svalue(), "a");
+        assert_eq!(df.argmax("c").unwrap(), Some(2));
+
+        let nulls = frame(vec![("x", vec![null(), null()])]);
+        assert!(nulls.min("x").unwrap().is_null());
+        assert_eq!(nulls.argmax("x").unwrap(), None);
+
+        assert!(matches!(df.min("y"), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(df.max("y"), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(df.argmin("y"), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(df.argmax("y"), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(df.median("y"), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(df.mode("y"), Err(DataFrameError::ColumnNotFound(_))));
+    }
+
+    #[test]
+    fn quantile_interpolations_match_numpy() {
+        let df = frame(vec![("x", vec![i(10), i(2), null(), i(4), i(1), i(3)])]);
+        let q = |q, interpolation| df.quantile("x", q, interpolation).unwrap().fvalue();
+
+        // np.quantile([1, 2, 3, 4, 10], 0.3, method=...)
+        close(q(0.3, QuantileInterpolation::Linear), 2.2, 1e-12);
+        assert_eq!(q(0.3, QuantileInterpolation::Lower), 2.0);
+        assert_eq!(q(0.3, QuantileInterpolation::Higher), 3.0);
+        assert_eq!(q(0.3, QuantileInterpolation::Midpoint), 2.5);
+        assert_eq!(q(0.3, QuantileInterpolation::Nearest), 2.0);
+        // halfway positions round to the even one: 1.5 -> 2, 2.5 -> 2
+        assert_eq!(q(0.375, QuantileInterpolation::Nearest), 3.0);
+        assert_eq!(q(0.625, QuantileInterpolation::Nearest), 3.0);
+        assert_eq!(q(0.0, QuantileInterpolation::Linear), 1.0);
+        assert_eq!(q(1.0, QuantileInterpolation::Linear), 10.0);
+        assert_eq!(df.median("x").unwrap().fvalue(), 3.0);
+    }
+
+    #[test]
+    fn quantile_rejects_levels_outside_the_unit_interval() {
+        let df = frame(vec![("x", floats(&[1.0, 2.0]))]);
+        assert!(matches!(df.quantile("x", 1.5, QuantileInterpolation::Linear), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.quantile("x", -0.1, QuantileInterpolation::Linear), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.quantile("x", f64::NAN, QuantileInterpolation::Linear), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.quantile("y", 0.5, QuantileInterpolation::Linear), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(df.group_by(vec!["x"]).quantile(2.0, QuantileInterpolation::Linear), Err(DataFrameError::InvalidArgument(_))));
+
+        let nulls = frame(vec![("x", vec![null(), f(f64::NAN)])]);
+        assert!(nulls.median("x").unwrap().is_null());
+    }
+
+    #[test]
+    fn quantiles_skip_string_cells_of_a_numeric_column() {
+        // load_csv keeps a blank field as an empty string
+        let df = frame(vec![("x", vec![f(1.0), s(""), f(3.0), f(2.0)])]);
+        assert_eq!(df.median("x").unwrap().fvalue(), 2.0);
+        assert_eq!(df.quantile("x", 1.0, QuantileInterpolation::Linear).unwrap().fvalue(), 3.0);
+        assert_eq!(df.group_by(vec!["x"]).median()[0].iter().filter(|v| v.is_null()).count(), 1);
+    }
+
+    #[test]
+    fn mode_lists_every_most_frequent_value() {
+        let df = frame(vec![("x", vec![i(3), i(1), i(3), null(), null(), null(), i(1), i(2)])]);
+        assert_eq!(text(&df.mode("x").unwrap(), "x"), vec!["1", "3"]);
+
+        let nulls = frame(vec![("x", vec![null()])]);
+        assert_eq!(nulls.mode("x").unwrap().nrows(), 0);
+    }
+
+    #[test]
+    fn group_order_statistics() {
+        let df = frame(vec![("g", ints(&[1, 2, 2, 2, 1]))]);
+        let group = df.group_by(vec!["g"]);
+
+        let mut medians : Vec<f64> = group.median()[0].iter().map(|v| v.fvalue()).collect();
+        medians.sort_by(|l, r| l.partial_cmp(r).unwrap());
+        assert_eq!(medians, vec![1.0, 2.0]);
+
+        let mut argmins : Vec<Option<usize>> = group.argmin()[0].clone();
+        argmins.sort();
+        assert_eq!(argmins, vec![Some(0), Some(1)]);
+    }
+
+
+    // higher moments
+
+    #[test]
+    fn moments_match_scipy() {
+        // scipy.stats.skew/kurtosis with bias=True and bias=False,
+        // scipy.stats.sem and scipy.stats.variation
+        let df = frame(vec![("x", vec![i(2), i(8), i(0), i(4), null(), i(1), i(9), i(9), i(0)])]);
+        close(df.skewness("x", false).unwrap().fvalue(), 0.2650554122698573, 1e-12);
+        close(df.skewness("x", true).unwrap().fvalue(), 0.33058218040797466, 1e-12);
+        close(df.kurtosis("x", false).unwrap().fvalue(), -1.6660010752838508, 1e-12);
+        close(df.kurtosis("x", true).unwrap().fvalue(), -2.098602258096087, 1e-12);
+        close(df.sem("x").unwrap().fvalue(), 1.407093001292279, 1e-12);
+        close(df.cv("x").unwrap().fvalue(), 0.9025013704142686, 1e-12);
+    }
+
+    #[test]
+    fn moments_survive_a_large_offset() {
+        let shifted : Vec<f64> = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0].iter().map(|v| v + 1.0e9).collect();
+        let df = frame(vec![("x", floats(&shifted))]);
+        close(df.skewness("x", false).unwrap().fvalue(), 0.2650554122698573, 1e-6);
+        close(df.kurtosis("x", false).unwrap().fvalue(), -1.6660010752838508, 1e-6);
+    }
+
+    #[test]
+    fn moments_are_nan_without_enough_spread_or_values() {
+        let constant = frame(vec![("x", floats(&[3.0, 3.0, 3.0, 3.0]))]);
+        assert!(constant.skewness("x", false).unwrap().fvalue().is_nan());
+        assert!(constant.kurtosis("x", true).unwrap().fvalue().is_nan());
+        assert_eq!(constant.sem("x").unwrap().fvalue(), 0.0);
+
+        let short = frame(vec![("x", floats(&[1.0, 2.0, 4.0]))]);
+        assert!(!short.skewness("x", true).unwrap().fvalue().is_nan());
+        assert!(short.kurtosis("x", true).unwrap().fvalue().is_nan());
+
+        let nulls = frame(vec![("x", vec![null(), null()])]);
+        assert!(nulls.skewness("x", false).unwrap().is_null());
+        assert!(nulls.sem("x").unwrap().is_null());
+
+        assert!(matches!(constant.skewness("y", false), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(constant.kurtosis("y", false), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(constant.sem("y"), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(constant.cv("y"), Err(DataFrameError::ColumnNotFound(_))));
+    }
+
+    #[test]
+    fn group_moments() {
+        let df = frame(vec![("g", floats(&[1.0, 1.0, 1.0]))]);
+        let group = df.group_by(vec!["g"]);
+        assert_eq!(group.sem()[0][0].fvalue(), 0.0);
+        assert!(group.skewness(false)[0][0].fvalue().is_nan());
+        assert_eq!(group.cv()[0][0].fvalue(), 0.0);
+    }
+
+
+    // correlation
+
+    fn paired() -> DataFrame {
+        frame(vec![
+            ("x", vec![i(1), i(2), null(), i(3), i(4), i(5)]),
+            ("y", vec![f(5.0), f(6.0), f(9.0), f(7.0), f(8.0), f(7.0)]),
+            ("label", strs(&["a", "b", "c", "d", "e", "f"])),
+        ])
+    }
+
+    #[test]
+    fn correlations_match_scipy() {
+        // scipy.stats.pearsonr, spearmanr and kendalltau (tau-b) of
+        // [1, 2, 3, 4, 5] and [5, 6, 7, 8, 7], np.cov
+        let df = paired();
+        close(df.corr("x", "y", CorrelationMethod::Pearson).unwrap().fvalue(), 0.8320502943378437, 1e-12);
+        close(df.corr("x", "y", CorrelationMethod::Spearman).unwrap().fvalue(), 0.8207826816681233, 1e-12);
+        close(df.corr("x", "y", CorrelationMethod::Kendall).unwrap().fvalue(), 0.7378647873726218, 1e-12);
+        close(df.cov("x", "y").unwrap().fvalue(), 1.5, 1e-12);
+    }
+
+    #[test]
+    fn correlation_matrices_cover_numeric_columns() {
+        let df = paired();
+        let cov = df.cov_matrix();
+        assert_eq!(cov.labels, vec!["column", "x", "y"]);
+        assert_eq!(text(&cov, "column"), vec!["x", "y"]);
+        all_close(&values(&cov, "x"), &[2.5, 1.5], 1e-12);
+        // pairwise complete rows, so y against itself keeps the row x lacks
+        all_close(&values(&cov, "y"), &[1.5, 2.0], 1e-12);
+
+        let corr = df.corr_matrix(CorrelationMethod::Kendall);
+        all_close(&values(&corr, "x"), &[1.0, 0.7378647873726218], 1e-12);
+    }
+
+    #[test]
+    fn correlation_ignores_strings_and_short_input() {
+        // string cells used to reach the rank sort as NaN and panic
+        let df = frame(vec![("x", vec![f(1.0), s("two"), f(3.0), f(4.0)]), ("y", floats(&[2.0, 1.0, 4.0, 3.0]))]);
+        close(df.corr("x", "y", CorrelationMethod::Spearman).unwrap().fvalue(), 0.5, 1e-12);
+        close(df.corr("x", "y", CorrelationMethod::Kendall).unwrap().fvalue(), 1.0 / 3.0, 1e-12);
+
+        let one = frame(vec![("x", floats(&[1.0])), ("y", floats(&[2.0]))]);
+        assert!(one.corr("x", "y", CorrelationMethod::Pearson).unwrap().fvalue().is_nan());
+        assert!(one.cov("x", "y").unwrap().fvalue().is_nan());
+    }
+
+    #[test]
+    fn kendall_and_spearman_with_ties() {
+        // scipy.stats.kendalltau([1, 1, 2, 3], [1, 2, 2, 3]) and
+        // spearmanr of the same
+        let df = frame(vec![("x", ints(&[1, 1, 2, 3])), ("y", ints(&[1, 2, 2, 3]))]);
+        close(df.corr("x", "y", CorrelationMethod::Kendall).unwrap().fvalue(), 0.8, 1e-12);
+        close(df.corr("x", "y", CorrelationMethod::Spearman).unwrap().fvalue(), 0.8333333333333334, 1e-12);
+    }
+
+    #[test]
+    fn rolling_correlation_windows() {
+        let df = frame(vec![("x", ints(&[1, 2, 3, 4, 5])), ("y", ints(&[5, 6, 7, 8, 7]))]);
+        let rolling = df.rolling_corr("x", "y", 3).unwrap();
+        assert_eq!(rolling.labels, vec!["x_y"]);
+        all_close(&values(&rolling, "x_y"), &[1.0, 1.0, 0.0], 1e-12);
+
+        assert!(matches!(df.rolling_corr("x", "y", 0), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.rolling_corr("x", "z", 3), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(df.corr("z", "y", CorrelationMethod::Pearson), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(df.cov("x", "z"), Err(DataFrameError::ColumnNotFound(_))));
+        assert_eq!(df.rolling_corr("x", "y", 6).unwrap().nrows(), 0);
+        assert!(values(&df.rolling_corr("x", "y", 1).unwrap(), "x_y").iter().all(|v| v.is_nan()));
+    }
+
+
+    // integer statistics
+
+    #[test]
+    fn integer_statistics_are_floats() {
+        let df = frame(vec![("x", vec![i(1), i(2), null(), i(2)])]);
+        assert!(matches!(df.mean("x"), DataTypes::FloatType(_)));
+        close(df.mean("x").fvalue(), 5.0 / 3.0, 1e-12);
+        close(df.stddev("x", 1).fvalue(), (1.0f64 / 3.0).sqrt(), 1e-12);
+        close(df.pstddev("x").fvalue(), (2.0f64 / 9.0).sqrt(), 1e-12);
+        close(df.variance("x", 0).fvalue(), 2.0 / 9.0, 1e-12);
+        assert!(matches!(df.sum("x"), DataTypes::IntegerType(IntegerType{value : 5})));
+
+        let rolling = df.simple_rolling_mean("x", 2);
+        assert!(rolling.series("x").iter().all(|v| matches!(v, DataTypes::FloatType(_))));
+        all_close(&values(&rolling, "x"), &[1.5, 2.0, 2.0], 1e-12);
+    }
+
+    #[test]
+    fn integer_diff_and_pct_change() {
+        let df = frame(vec![("x", ints(&[2, 3, 6]))]);
+        assert_eq!(values(&df.diff("x"), "x"), vec![1.0, 3.0]);
+        assert_eq!(values(&df.pct_change("x"), "x"), vec![0.5, 1.0]);
+        assert_eq!(frame(vec![("x", ints(&[]))]).diff("x").nrows(), 0);
+    }
+
+
+    // ddof and rolling statistics
+
+    #[test]
+    fn variance_and_stddev_take_ddof() {
+        let df = frame(vec![("x", floats(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]))]);
+        assert_eq!(df.variance("x", 0).fvalue(), 4.0);
+        assert_eq!(df.stddev("x", 0).fvalue(), 2.0);
+        close(df.variance("x", 1).fvalue(), 32.0 / 7.0, 1e-12);
+        assert_eq!(df.pstddev("x").fvalue(), 2.0);
+
+        let one = frame(vec![("x", floats(&[3.0]))]);
+        assert!(one.variance("x", 1).fvalue().is_nan());
+        assert_eq!(one.variance("x", 0).fvalue(), 0.0);
+    }
+
+    #[test]
+    fn rolling_statistics_match_pandas() {
+        // pd.Series([1, 2, 4, 7, 11]).rolling(3).var(ddof=...), without
+        // the leading partial windows
+        let df = frame(vec![("x", ints(&[1, 2, 4, 7, 11]))]);
+        all_close(&values(&df.rolling_variance("x", 3, 1), "x"), &[42.0 / 18.0, 114.0 / 18.0, 222.0 / 18.0], 1e-12);
+        all_close(&values(&df.rolling_variance("x", 3, 0), "x"), &[42.0 / 27.0, 114.0 / 27.0, 222.0 / 27.0], 1e-12);
+        all_close(&values(&df.rolling_stddev("x", 3, 1), "x"), &[(42.0f64 / 18.0).sqrt(), (114.0f64 / 18.0).sqrt(), (222.0f64 / 18.0).sqrt()], 1e-12);
+        all_close(&values(&df.simple_rolling_mean("x", 2), "x"), &[1.5, 3.0, 5.5, 9.0], 1e-12);
+    }
+
+    #[test]
+    fn rolling_statistics_skip_nulls_in_a_window() {
+        let df = frame(vec![("x", vec![f(1.0), null(), f(3.0), f(f64::NAN), f(f64::NAN)])]);
+        all_close(&values(&df.simple_rolling_mean("x", 2), "x"), &[1.0, 3.0, 3.0, f64::NAN], 1e-12);
+        all_close(&values(&df.rolling_variance("x", 3, 1), "x"), &[2.0, f64::NAN, f64::NAN], 1e-12);
+    }
+
+    #[test]
+    fn rolling_statistics_with_zero_or_oversized_windows() {
+        let numbers = frame(vec![("x", floats(&[1.0, 2.0]))]);
+        let nulls = frame(vec![("x", vec![null(), null()])]);
+        let words = frame(vec![("x", strs(&["a", "b"]))]);
+
+        for df in [&numbers, &nulls, &words] {
+            assert_eq!(df.simple_rolling_mean("x", 0).nrows(), 0);
+            assert_eq!(df.rolling_stddev("x", 0, 1).nrows(), 0);
+            assert_eq!(df.rolling_variance("x", 0, 1).nrows(), 0);
+        }
+
+        assert_eq!(numbers.rolling_variance("x", 3, 1).nrows(), 0);
+        assert_eq!(nulls.rolling_stddev("x", 1, 1).nrows(), 2);
+        assert!(nulls.rolling_stddev("x", 1, 1).series("x").iter().all(|v| v.is_null()));
+    }
+
+    #[test]
+    fn group_rolling_statistics() {
+        let df = frame(vec![("g", floats(&[2.0, 2.0, 2.0]))]);
+        let group = df.group_by(vec!["g"]);
+        let rolling : Vec<f64> = group.rolling_variance(2, 1)[0][0].iter().map(|v| v.fvalue()).collect();
+        assert_eq!(rolling, vec![0.0, 0.0]);
+        assert!(group.rolling_stddev(0, 1)[0][0].is_empty());
+        assert_eq!(group.variance(0)[0][0].fvalue(), 0.0);
+    }
+
+
+    // summation
+
+    #[test]
+    fn compensated_sum_recovers_lost_terms() {
+        assert_eq!(compensated_sum([1.0e16, 1.0, -1.0e16].iter().cloned()), 1.0);
+        assert_eq!(compensated_sum(std::iter::repeat_n(0.1, 10)), 1.0);
+        assert_eq!(compensated_sum(std::iter::empty()), 0.0);
+
+        let df = frame(vec![("x", floats(&[1.0e16, 1.0, -1.0e16, 1.0]))]);
+        assert_eq!(df.sum("x").fvalue(), 2.0);
+        assert_eq!(df.mean("x").fvalue(), 0.5);
+    }
+
+    #[test]
+    fn integer_sums_do_not_overflow() {
+        let df = frame(vec![("x", ints(&[i64::MAX, i64::MAX, -5]))]);
+        assert!(matches!(df.sum("x"), DataTypes::FloatType(_)));
+        close(df.sum("x").fvalue(), 2.0 * i64::MAX as f64, 1e-15);
+        close(df.mean("x").fvalue(), 2.0 * i64::MAX as f64 / 3.0, 1e-15);
+
+        let fits = frame(vec![("x", ints(&[i64::MAX, 1, -1]))]);
+        assert!(matches!(fits.sum("x"), DataTypes::IntegerType(IntegerType{value : i64::MAX})));
+    }
+
+    #[test]
+    fn float_sums_include_integer_cells() {
+        let df = frame(vec![("x", vec![f(1.5), i(2), null(), f(f64::NAN), s("3")])]);
+        assert_eq!(df.sum("x").fvalue(), 3.5);
+        assert_eq!(df.mean("x").fvalue(), 1.75);
+    }
+
+    #[test]
+    fn mixed_sums_do_not_depend_on_the_first_cell() {
+        let int_first = frame(vec![("x", vec![i(2), null(), f(1.5), s("3")])]);
+        let float_first = frame(vec![("x", vec![f(1.5), null(), i(2), s("3")])]);
+        for df in [&int_first, &float_first] {
+            assert!(matches!(df.sum("x"), DataTypes::FloatType(_)));
+            assert_eq!(df.sum("x").fvalue(), 3.5);
+            assert_eq!(df.mean("x").fvalue(), 1.75);
+        }
+    }
+
+    #[test]
+    fn correlation_survives_a_large_offset() {
+        let x : Vec<f64> = [1.0, 2.0, 3.0, 4.0, 5.0].iter().map(|v| v + 1.0e12).collect();
+        let df = frame(vec![("x", floats(&x)), ("y", floats(&[5.0, 6.0, 7.0, 8.0, 7.0]))]);
+        close(df.corr("x", "y", CorrelationMethod::Pearson).unwrap().fvalue(), 0.8320502943378437, 1e-9);
+        close(df.cov("x", "x").unwrap().fvalue(), 2.5, 1e-9);
+    }
+
+
+    // binning
+
+    #[test]
+    fn cut_equal_width_bins_like_pandas() {
+        // pd.cut([1, 2, 3, 4, 5, 6], 3): the first edge is moved down by
+        // 0.1% of the range so the minimum falls in the first bin
+        let df = frame(vec![("x", vec![i(1), i(2), i(3), null(), i(4), i(5), i(6)])]);
+        let binned = df.cut("x", Bins::Count(3), None, true).unwrap();
+        assert_eq!(text(&binned, "x"), vec!["(0.995, 2.667]", "(0.995, 2.667]", "(2.667, 4.333]", "null", "(2.667, 4.333]", "(4.333, 6]", "(4.333, 6]"]);
+
+        let left = df.cut("x", Bins::Count(2), Some(vec!["low", "high"]), false).unwrap();
+        assert_eq!(text(&left, "x"), vec!["low", "low", "low", "null", "high", "high", "high"]);
+    }
+
+    #[test]
+    fn cut_with_explicit_edges() {
+        let df = frame(vec![("x", floats(&[0.0, 1.0, 1.5, 3.0, 9.0]))]);
+        let binned = df.cut("x", Bins::Edges(vec![0.0, 1.0, 3.0]), None, true).unwrap();
+        assert_eq!(text(&binned, "x"), vec!["null", "(0, 1]", "(1, 3]", "(1, 3]", "null"]);
+
+        assert!(df.cut("x", Bins::Edges(vec![1.0, 1.0]), None, true).is_err());
+        assert!(df.cut("x", Bins::Edges(vec![0.0, 1.0]), Some(vec!["a", "b"]), true).is_err());
+        assert!(df.cut("x", Bins::Count(0), None, true).is_err());
+        assert!(frame(vec![("x", vec![null()])]).cut("x", Bins::Count(2), None, true).is_err());
+    }
+
+    #[test]
+    fn qcut_uses_quantile_edges() {
+        let df = frame(vec![("x", ints(&[1, 2, 3, 4, 5, 6, 7, 8]))]);
+        let binned = df.qcut("x", Bins::Count(4), Some(vec!["q1", "q2", "q3", "q4"]), false).unwrap();
+        assert_eq!(text(&binned, "x"), vec!["q1", "q1", "q2", "q2", "q3", "q3", "q4", "q4"]);
+
+        let named = df.qcut("x", Bins::Edges(vec![0.0, 0.5, 1.0]), None, false).unwrap();
+        assert_eq!(text(&named, "x")[..5], ["[1, 4.5]", "[1, 4.5]", "[1, 4.5]", "[1, 4.5]", "(4.5, 8]"]);
+
+        assert!(df.qcut("x", Bins::Edges(vec![0.0, 1.5]), None, false).is_err());
+    }
+
+    #[test]
+    fn qcut_collapses_repeated_edges_on_request() {
+        let df = frame(vec![("x", ints(&[1, 1, 1, 1, 1, 1, 2, 3]))]);
+        assert!(matches!(df.qcut("x", Bins::Count(4), None, false), Err(DataFrameError::InvalidArgument(_))));
+
+        let binned = df.qcut("x", Bins::Count(4), None, true).unwrap();
+        assert_eq!(text(&binned, "x"), vec!["[1, 1.25]", "[1, 1.25]", "[1, 1.25]", "[1, 1.25]", "[1, 1.25]", "[1, 1.25]", "(1.25, 3]", "(1.25, 3]"]);
+
+        let constant = frame(vec![("x", ints(&[4, 4]))]);
+        assert!(constant.qcut("x", Bins::Count(2), None, true).is_err());
+    }
+
+    #[test]
+    fn binning_rejects_a_string_anywhere_in_the_column() {
+        // load_csv keeps a blank field as an empty string
+        let df = frame(vec![("x", vec![f(1.0), s(""), f(3.0), f(2.0)])]);
+        assert!(matches!(df.qcut("x", Bins::Count(2), None, false), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.cut("x", Bins::Count(2), None, true), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.histogram("x", Bins::Count(2)), Err(DataFrameError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn histogram_matches_numpy() {
+        // np.histogram([1, 2, 1], bins=[0, 1, 2, 3])
+        let df = frame(vec![("x", ints(&[1, 2, 1]))]);
+        assert_eq!(df.histogram("x", Bins::Edges(vec![0.0, 1.0, 2.0, 3.0])).unwrap(), (vec![0.0, 1.0, 2.0, 3.0], vec![0, 2, 1]));
+
+        // np.histogram([1, 2, 2, 3, 10], bins=3)
+        let df = frame(vec![("x", vec![i(1), i(2), i(2), null(), i(3), i(10)])]);
+        assert_eq!(df.histogram("x", Bins::Count(3)).unwrap(), (vec![1.0, 4.0, 7.0, 10.0], vec![4, 0, 1]));
+
+        let empty = frame(vec![("x", vec![null()])]);
+        assert_eq!(empty.histogram("x", Bins::Count(2)).unwrap().1, vec![0, 0]);
+        assert!(df.histogram("x", Bins::Count(0)).is_err());
     }
 
-    pub fn rolling_stddev(&self, column_name : &str, window : usize) -> DataFrame {
-        let idx : usize = self.get_column_index(column_name);
 
-        let op = match self.columns[idx].first().unwrap() {
-            DataTypes::FloatType(FloatType{value : _}) => { FloatType::rolling_stddev },
-            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::rolling_stddev },
-            DataTypes::StringType(StringType{value : _}) => { StringType::rolling_stddev },
-        };
+    // ranking
 
-        DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![op(&self.columns[idx], window),] }
+    #[test]
+    fn rank_methods_match_pandas() {
+        // pd.Series([3, 1, 4, 1, 5, None]).rank(method=...)
+        let df = frame(vec![("x", vec![i(3), i(1), i(4), i(1), i(5), null()])]);
+        let rank = |method, ascending, pct, nulls| values(&df.rank("x", method, ascending, pct, nulls).unwrap(), "x");
+        let nan = f64::NAN;
+
+        all_close(&rank(RankMethod::Average, true, false, NullRank::Keep), &[3.0, 1.5, 4.0, 1.5, 5.0, nan], 0.0);
+        all_close(&rank(RankMethod::Min, true, false, NullRank::Keep), &[3.0, 1.0, 4.0, 1.0, 5.0, nan], 0.0);
+        all_close(&rank(RankMethod::Max, true, false, NullRank::Keep), &[3.0, 2.0, 4.0, 2.0, 5.0, nan], 0.0);
+        all_close(&rank(RankMethod::First, true, false, NullRank::Keep), &[3.0, 1.0, 4.0, 2.0, 5.0, nan], 0.0);
+        all_close(&rank(RankMethod::Dense, true, false, NullRank::Keep), &[2.0, 1.0, 3.0, 1.0, 4.0, nan], 0.0);
+        all_close(&rank(RankMethod::Average, false, false, NullRank::Keep), &[3.0, 4.5, 2.0, 4.5, 1.0, nan], 0.0);
     }
-    
-    pub fn rolling_variance(&self, column_name : &str, window : usize) -> DataFrame {
-        let idx : usize = self.get_column_index(column_name);
 
-        let op = match self.columns[idx].first().unwrap() {
-            DataTypes::FloatType(FloatType{value : _}) => { FloatType::rolling_variance },
-            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::rolling_variance },
-            DataTypes::StringType(StringType{value : _}) => { StringType::rolling_variance },
-        };
+    #[test]
+    fn rank_percentages_and_null_placement() {
+        let df = frame(vec![("x", vec![i(3), i(1), i(4), i(1), i(5), null()])]);
+        let rank = |method, pct, nulls| values(&df.rank("x", method, true, pct, nulls).unwrap(), "x");
 
-        DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![op(&self.columns[idx], window),] }
+        all_close(&rank(RankMethod::Average, true, NullRank::Keep), &[0.6, 0.3, 0.8, 0.3, 1.0, f64::NAN], 1e-12);
+        all_close(&rank(RankMethod::Dense, true, NullRank::Keep), &[0.5, 0.25, 0.75, 0.25, 1.0, f64::NAN], 1e-12);
+        all_close(&rank(RankMethod::Average, false, NullRank::Top), &[4.0, 2.5, 5.0, 2.5, 6.0, 1.0], 0.0);
+        all_close(&rank(RankMethod::Average, false, NullRank::Bottom), &[3.0, 1.5, 4.0, 1.5, 5.0, 6.0], 0.0);
     }
 
-    pub fn diff(&self, column_name : &str) -> DataFrame {
-        let idx : usize = self.get_column_index(column_name);
+    #[test]
+    fn rank_ties_strings_and_empty_input() {
+        let words = frame(vec![("x", strs(&["b", "a", "b"]))]);
+        assert_eq!(values(&words.rank("x", RankMethod::Average, true, false, NullRank::Keep).unwrap(), "x"), vec![2.5, 1.0, 2.5]);
 
-        let op = match self.columns[idx].first().unwrap() {
-            DataTypes::FloatType(FloatType{value : _}) => { FloatType::diff },
-            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::diff },
-            DataTypes::StringType(StringType{value : _}) => { StringType::diff },
-        };
+        let nulls = frame(vec![("x", vec![null(), null()])]);
+        assert!(nulls.rank("x", RankMethod::Min, true, false, NullRank::Keep).unwrap().series("x").iter().all(|v| v.is_null()));
+        assert_eq!(values(&nulls.rank("x", RankMethod::Min, true, false, NullRank::Top).unwrap(), "x"), vec![1.0, 1.0]);
 
-        DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![op(&self.columns[idx],),] }
+        assert_eq!(frame(vec![("x", Series::new())]).rank("x", RankMethod::First, true, true, NullRank::Keep).unwrap().nrows(), 0);
+        assert!(matches!(words.rank("y", RankMethod::First, true, false, NullRank::Keep), Err(DataFrameError::ColumnNotFound(_))));
     }
 
-    pub fn pct_change(&self, column_name : &str) -> DataFrame {
-        let idx : usize = self.get_column_index(column_name);
+    #[test]
+    fn group_rank() {
+        let df = frame(vec![("g", ints(&[7, 7]))]);
+        let ranks = df.group_by(vec!["g"]).rank(RankMethod::Average, true, false, NullRank::Keep);
+        assert_eq!(ranks[0][0].iter().map(|v| v.fvalue()).collect::<Vec<f64>>(), vec![1.5, 1.5]);
+    }
 
-        let op = match self.columns[idx].first().unwrap() {
-            DataTypes::FloatType(FloatType{value : _}) => { FloatType::pct_change },
-            DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::pct_change },
-            DataTypes::StringType(StringType{value : _}) => { StringType::pct_change },
-        };
 
-        DataFrame{ labels : std::vec![column_name.to_string(),], columns : vec![op(&self.columns[idx],),] }
+    // regression, with reference fits from R's lm
+
+    #[test]
+    fn ols_matches_reference_fit() {
+        let df = frame(vec![
+            ("y", ints(&[1, 3, 2, 5, 7, 8, 8, 9, 10, 12])),
+            ("x1", ints(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10])),
+            ("x2", ints(&[2, 1, 4, 3, 6, 5, 8, 7, 10, 9])),
+        ]);
+        let fit = df.ols("y", vec!["x1", "x2"]).unwrap();
+        close(fit.intercept, 0.175, 1e-12);
+        all_close(&fit.coefficients, &[1.475, -0.325], 1e-12);
+        close(fit.intercept_error, 0.5605322725512141, 1e-12);
+        all_close(&fit.standard_errors, &[0.2603226020591703, 0.2603226020591703], 1e-12);
+        close(fit.r_squared, 0.9611814345991562, 1e-12);
+        let residuals : Vec<f64> = fit.residuals.iter().map(|v| v.fvalue()).collect();
+        all_close(&residuals, &[0.0, 0.2, -1.3, -0.1, 1.4, 0.6, 0.1, -0.7, -0.2, 0.0], 1e-12);
     }
 
-    pub fn group_by(&self, column_names : Vec<&str>) -> Group {
-        Group::new(self, column_names)
+    #[test]
+    fn ols_skips_null_rows_and_rejects_bad_input() {
+        let df = frame(vec![
+            ("y", vec![f(2.1), f(3.9), null(), f(6.2), f(7.8), f(10.1), f(12.2), f(13.8), f(16.1)]),
+            ("x", vec![i(0), i(1), i(9), i(2), i(3), i(4), i(5), i(6), i(7)]),
+            ("twice", vec![i(0), i(2), i(18), i(4), i(6), i(8), i(10), i(12), i(14)]),
+            ("name", strs(&["a", "b", "c", "d", "e", "f", "g", "h", "i"])),
+        ]);
+        let fit = df.ols("y", vec!["x"]).unwrap();
+        close(fit.intercept, 2.033333333333333, 1e-12);
+        all_close(&fit.coefficients, &[1.9976190476190478], 1e-12);
+        assert!(fit.residuals[2].is_null());
+        close(fit.residuals[0].fvalue(), 0.06666666666666705, 1e-9);
+
+        assert!(matches!(df.ols("y", vec!["x", "twice"]), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.ols("y", vec!["name"]), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.ols("y", vec!["missing"]), Err(DataFrameError::ColumnNotFound(_))));
+
+        let empty = frame(vec![("y", Vec::new()), ("x", Vec::new())]);
+        assert!(matches!(empty.ols("y", vec!["x"]), Err(DataFrameError::InvalidArgument(_))));
+        let nulls = frame(vec![("y", vec![null(), null()]), ("x", ints(&[1, 2]))]);
+        assert!(matches!(nulls.ols("y", vec!["x"]), Err(DataFrameError::InvalidArgument(_))));
     }
 
-    pub fn println(&self) {
-        let col_count = self.labels.len();
-        for i in 0..col_count {
-            println!("{}", self.labels[i]);
-            let col_len = self.columns[i].len();
-            for j in 0..col_len {
-                self.columns[i][j].println();
-            }
-        }
+    #[test]
+    fn linear_trend_over_numbers_and_dates() {
+        let values = floats(&[2.1, 3.9, 6.2, 7.8, 10.1, 12.2, 13.8, 16.1]);
+        let df = frame(vec![("t", ints(&[0, 1, 2, 3, 4, 5, 6, 7])), ("v", values.clone())]);
+        let fit = df.linear_trend("t", "v").unwrap();
+        close(fit.intercept, 2.033333333333333, 1e-12);
+        all_close(&fit.coefficients, &[1.9976190476190478], 1e-12);
+        all_close(&fit.standard_errors, &[0.027800444266884102], 1e-9);
+        close(fit.r_squared, 0.9988392866011389, 1e-12);
+
+        let days = strs(&["2000-01-01", "2000-01-02", "2000-01-03", "2000-01-04", "2000-01-05", "2000-01-06", "2000-01-07", "2000-01-08"]);
+        let df = frame(vec![("day", days), ("v", values)]);
+        let fit = df.linear_trend("day", "v").unwrap();
+        close(fit.coefficients[0] * 86400.0, 1.9976190476190478, 1e-9);
+        close(fit.r_squared, 0.9988392866011389, 1e-9);
+
+        let df = frame(vec![("t", strs(&["a", "b"])), ("v", ints(&[1, 2]))]);
+        assert!(matches!(df.linear_trend("t", "v"), Err(DataFrameError::InvalidArgument(_))));
     }
-}
 
-pub struct BloomFilter {
-    size : usize,    
-    item_count : usize,
-    hash_count : usize,
-    false_probability : f64,
-    bit_array : Vec<bool>,
-}
+    #[test]
+    fn theil_sen_resists_outliers_and_skips_tied_times() {
+        let df = frame(vec![("t", ints(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9])), ("v", ints(&[1, 3, 5, 7, 9, 11, 13, 15, 17, 100]))]);
+        assert_eq!(df.theil_sen("t", "v").unwrap(), (2.0, 1.0));
 
-impl BloomFilter {
+        // slopes -1, 0.5, 1, 1.5, 2 between distinct times, intercept
+        // the median of v - t
+        let df = frame(vec![("t", ints(&[1, 1, 2, 3])), ("v", ints(&[1, 3, 2, 4]))]);
+        assert_eq!(df.theil_sen("t", "v").unwrap(), (1.0, 0.5));
 
-    pub fn new(itemcount : usize, fprob : f64) -> BloomFilter {
-        let log2 = 2_f64.log(10.0);
-        let sz = (-(itemcount as f64 * fprob.log(10.0))/log2.powf(2.0)) as usize;
-        let hc = ((sz / itemcount) as f64 * log2) as usize;
-        BloomFilter{size : sz, item_count : itemcount, hash_count : hc, false_probability : fprob, bit_array : Vec::new() }
+        let df = frame(vec![("t", ints(&[1, 1])), ("v", ints(&[1, 3]))]);
+        let (slope, intercept) = df.theil_sen("t", "v").unwrap();
+        assert!(slope.is_nan() && intercept.is_nan());
+
+        let df = frame(vec![("t", vec![i(1), null()]), ("v", ints(&[1, 3]))]);
+        assert!(matches!(df.theil_sen("t", "v"), Err(DataFrameError::InvalidArgument(_))));
     }
 
-    pub fn add<T>(&mut self, item : T)
-        where T : Hash
-    {
-        if self.bit_array.len() < 1 {
-            self.bit_array.resize(self.size, false);
-        }
+    #[test]
+    fn theil_sen_caps_the_number_of_points() {
+        let n = THEIL_SEN_MAX_POINTS as i64 + 1;
+        let df = frame(vec![("t", (0..n).map(i).collect()), ("v", (0..n).map(i).collect())]);
+        assert!(matches!(df.theil_sen("t", "v"), Err(DataFrameError::InvalidArgument(_))));
+    }
 
-        let mut s = DefaultHasher::new();
-        for i in 0..self.hash_count {
-            item.hash(&mut s);
-            let h = s.finish() as usize ^ i;
-            let digest = (h as usize) % self.size;
-            self.bit_array[digest] = true;
-        }
+
+    // bootstrap
+
+    #[test]
+    fn splitmix_matches_reference_sequence() {
+        let mut rng = SplitMix64::new(0);
+        assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+        assert_eq!(rng.next_u64(), 0x06c4_5d18_8009_454f);
     }
 
-    pub fn contains<T>(& self, item : T) -> bool 
-        where T : Hash
-    {
-        if self.bit_array.len() < 1 {
-            return false;
+    #[test]
+    fn bootstrap_is_seeded_and_close_to_the_normal_interval() {
+        let df = frame(vec![("v", (1..=100).map(i).collect())]);
+        let ci = df.bootstrap("v", Aggregation::Mean, BootstrapMethod::Percentile, 0.95, 2000, 7).unwrap();
+        assert_eq!(ci, df.bootstrap("v", Aggregation::Mean, BootstrapMethod::Percentile, 0.95, 2000, 7).unwrap());
+        assert_ne!(ci, df.bootstrap("v", Aggregation::Mean, BootstrapMethod::Percentile, 0.95, 2000, 8).unwrap());
+
+        // the standard error of the mean of 1..=100 resampled is
+        // sqrt((100^2 - 1) / 12) / 10
+        let half = 1.959963984540054 * (9999.0f64 / 12.0).sqrt() / 10.0;
+        close(ci.estimate, 50.5, 1e-12);
+        assert!((ci.lower - (50.5 - half)).abs() < 0.5, "{:?}", ci);
+        assert!((ci.upper - (50.5 + half)).abs() < 0.5, "{:?}", ci);
+
+        let bca = df.bootstrap("v", Aggregation::Mean, BootstrapMethod::BCa, 0.95, 2000, 7).unwrap();
+        assert!(bca.lower < 50.5 && 50.5 < bca.upper, "{:?}", bca);
+        assert!((bca.lower - (50.5 - half)).abs() < 0.5, "{:?}", bca);
+    }
+
+    #[test]
+    fn bootstrap_of_constant_empty_and_null_columns() {
+        let df = frame(vec![("c", ints(&[3, 3, 3, 3])), ("n", vec![null(), null(), null(), null()])]);
+        for &method in [BootstrapMethod::Percentile, BootstrapMethod::BCa].iter() {
+            let ci = df.bootstrap("c", Aggregation::Median, method, 0.9, 50, 1).unwrap();
+            assert_eq!((ci.estimate, ci.lower, ci.upper), (3.0, 3.0, 3.0));
         }
 
-        let mut s = DefaultHasher::new();
-        for i in 0..self.hash_count {
-            item.hash(&mut s);
-            let h = s.finish() as usize ^ i;
-            let digest = (h as usize) % self.size;
-            if self.bit_array[digest] == false {
-                return false;
-            }
+        let ci = df.bootstrap("n", Aggregation::Mean, BootstrapMethod::Percentile, 0.9, 50, 1).unwrap();
+        assert!(ci.lower.is_nan() && ci.upper.is_nan());
+        let empty = frame(vec![("e", Vec::new())]);
+        let ci = empty.bootstrap("e", Aggregation::Mean, BootstrapMethod::BCa, 0.9, 50, 1).unwrap();
+        assert!(ci.lower.is_nan() && ci.upper.is_nan());
+    }
+
+    #[test]
+    fn bootstrap_rejects_bad_arguments() {
+        let df = frame(vec![("v", ints(&[1, 2, 3]))]);
+        for &confidence in [0.0, 1.0, -0.5, f64::NAN].iter() {
+            assert!(matches!(df.bootstrap("v", Aggregation::Mean, BootstrapMethod::Percentile, confidence, 10, 1), Err(DataFrameError::InvalidArgument(_))));
         }
+        assert!(matches!(df.bootstrap("v", Aggregation::Mean, BootstrapMethod::Percentile, 0.9, 0, 1), Err(DataFrameError::InvalidArgument(_))));
+        let quantile = Aggregation::Quantile(1.5, QuantileInterpolation::Linear);
+        assert!(matches!(df.bootstrap("v", quantile, BootstrapMethod::Percentile, 0.9, 10, 1), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.bootstrap("w", Aggregation::Mean, BootstrapMethod::Percentile, 0.9, 10, 1), Err(DataFrameError::ColumnNotFound(_))));
+
+        let group = df.group_by(vec!["v"]);
+        assert!(matches!(group.bootstrap("v", Aggregation::Mean, BootstrapMethod::Percentile, 0.9, 0, 1), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(group.bootstrap("v", quantile, BootstrapMethod::Percentile, 0.9, 10, 1), Err(DataFrameError::InvalidArgument(_))));
+    }
 
-        return true
+    #[test]
+    fn group_bootstrap_is_sorted_by_key_and_seeded() {
+        let df = frame(vec![("g", strs(&["b", "a", "b", "a", "c", "b"])), ("v", vec![i(1), i(5), i(2), i(5), null(), i(9)])]);
+        let group = df.group_by(vec!["g"]);
+        let intervals = group.bootstrap("v", Aggregation::Mean, BootstrapMethod::Percentile, 0.9, 200, 3).unwrap();
+        assert_eq!(intervals.len(), 1);
+
+        let keys : Vec<String> = intervals[0].iter().map(|(k, _)| k.svalue()).collect();
+        assert_eq!(keys, vec!["a", "b", "c"]);
+        assert_eq!(intervals[0][0].1, ConfidenceInterval{ estimate : 5.0, lower : 5.0, upper : 5.0 });
+        close(intervals[0][1].1.estimate, 4.0, 1e-12);
+        assert!(intervals[0][1].1.lower >= 1.0 && intervals[0][1].1.upper <= 9.0);
+        assert!(intervals[0][2].1.lower.is_nan());
+
+        let again = group.bootstrap("v", Aggregation::Mean, BootstrapMethod::Percentile, 0.9, 200, 3).unwrap();
+        assert_eq!(intervals[0][1].1, again[0][1].1);
     }
-}
 
-pub struct Group<'a> {
-    df : &'a DataFrame,    
-    column_indices : Vec<usize>,
-    indices : Vec< HashMap<DataTypes, Vec<usize>> >,
-}
 
-impl<'a> Group<'a> {
+    // correlograms, with reference values from statsmodels' acf and
+    // pacf(method="ldb")
 
-    pub fn new(df : &'a DataFrame, column_names : Vec<&str>) -> Group<'a> {
-        let column_idxs : Vec<usize> = column_names.iter().map(|&x| df.get_column_index(x)).collect();
+    #[test]
+    fn acf_with_bartlett_bounds() {
+        let df = frame(vec![("x", vec![i(2), i(4), null(), i(3), i(5), i(7), i(6), i(8), i(9), i(7), i(10)])]);
+        let acf = df.acf("x", 3, 0.95).unwrap();
+        assert_eq!(text(&acf, "lag"), vec!["0", "1", "2", "3"]);
+        all_close(&values(&acf, "acf"), &[1.0, 0.474384236453202, 0.43973727422003284, 0.1768472906403941], 1e-12);
+        all_close(&values(&acf, "lower"), &[1.0, -0.1454107958513596, -0.3066155718948644, -0.6631564482951406], 1e-9);
+        let upper = values(&acf, "upper");
+        close(upper[1], 0.474384236453202 * 2.0 + 0.1454107958513596, 1e-9);
 
-        let mut grp_indices : Vec< HashMap<DataTypes, Vec<usize>> > = vec![ HashMap::new(); column_idxs.len()];
-        for (grp_idx, columns) in column_idxs.iter().enumerate().map(|(y,x)| (y, df.columns.get(*x)) ) {
-            for (row_idx, coval) in columns.unwrap().iter().enumerate() {
-                grp_indices.get_mut(grp_idx).unwrap().entry(coval.clone()).or_insert(Vec::new()).push(row_idx);
-            }
-        };
+        let lag0 = df.acf("x", 0, 0.95).unwrap();
+        all_close(&values(&lag0, "acf"), &[1.0], 1e-12);
+    }
 
-        Group{df : df, column_indices : column_idxs, indices : grp_indices, }
+    #[test]
+    fn pacf_solves_the_yule_walker_equations() {
+        let df = frame(vec![("x", ints(&[2, 4, 3, 5, 7, 6, 8, 9, 7, 10]))]);
+        let pacf = df.pacf("x", 3, 0.95).unwrap();
+        all_close(&values(&pacf, "pacf"), &[1.0, 0.474384236453202, 0.27704266322554355, -0.1472991186595247], 1e-12);
+        let bound = 0.6197950323045616;
+        all_close(&values(&pacf, "upper"), &[1.0, 0.474384236453202 + bound, 0.27704266322554355 + bound, -0.1472991186595247 + bound], 1e-9);
     }
 
-    pub fn fields(&self) -> Vec< String >{
-        self.column_indices.iter().map(|&x| self.df.labels[x].to_string()).collect()
+    #[test]
+    fn ccf_pairs_x_with_later_y() {
+        let df = frame(vec![("x", ints(&[2, 4, 3, 5, 7, 6, 8, 9, 7, 10])), ("y", ints(&[1, 3, 2, 2, 5, 4, 6, 8, 6, 9]))]);
+        let ccf = df.ccf("x", "y", 2, 0.95).unwrap();
+        assert_eq!(text(&ccf, "lag"), vec!["-2", "-1", "0", "1", "2"]);
+        all_close(&values(&ccf, "ccf"), &[0.38929795404147366, 0.39025602940006626, 0.9644625276499182, 0.5339673331889614, 0.5090573738655529], 1e-12);
+        close(values(&ccf, "lower")[2], 0.9644625276499182 - 0.6197950323045616, 1e-9);
+
+        let swapped = df.ccf("y", "x", 2, 0.95).unwrap();
+        let mut reversed = values(&ccf, "ccf");
+        reversed.reverse();
+        all_close(&values(&swapped, "ccf"), &reversed, 1e-12);
     }
-    
-    pub fn size(&self) -> Vec< (String, Vec<(String, usize)>) > {
-        self.column_indices.iter().zip(self.indices.iter()).map(
-            |(x, y)| (
-                String::from(self.df.labels.get(*x).unwrap()),
-                y.iter().map( |z| (z.0.svalue(), z.1.len()) ).collect()
-            )
-        ).collect()
+
+    #[test]
+    fn correlograms_reject_short_constant_and_string_columns() {
+        let df = frame(vec![("x", ints(&[1, 2, 3])), ("c", ints(&[4, 4, 4])), ("n", vec![null(), null(), null()]), ("s", strs(&["a", "b", "c"]))]);
+        assert!(matches!(df.acf("x", 3, 0.95), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.pacf("x", 3, 0.95), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.ccf("x", "c", 3, 0.95), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.acf("x", 1, 1.0), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.ccf("x", "c", 1, 0.0), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.acf("n", 0, 0.95), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.acf("s", 1, 0.95), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.ccf("x", "s", 1, 0.95), Err(DataFrameError::InvalidArgument(_))));
+
+        let constant = df.acf("c", 1, 0.95).unwrap();
+        assert!(values(&constant, "acf").iter().all(|v| v.is_nan()));
+
+        let empty = frame(vec![("x", Vec::new())]);
+        assert!(matches!(empty.acf("x", 0, 0.95), Err(DataFrameError::InvalidArgument(_))));
     }
 
-    pub fn mean(&self) -> Vec< Vec<DataTypes> > {
-        self.column_indices.iter().enumerate().map( |(i, &ci)| {
 
-            let op = match self.df.columns[ci].first().unwrap() {
-                DataTypes::FloatType(FloatType{value : _}) => { FloatType::mean },
-                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::mean },
-                DataTypes::StringType(StringType{value : _}) => { StringType::mean },
-            };
+    // seasonal decomposition, with classical reference values from
+    // statsmodels' seasonal_decompose
 
-            let data : Vec<Vec<DataTypes>> = self.indices[i].iter().map( |(_, y)|
-                y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()
-            ).collect();
+    const QUARTERS : [i64; 16] = [10, 14, 8, 25, 16, 22, 14, 35, 15, 27, 18, 40, 28, 40, 25, 65];
 
-            data.iter().map(|x| op(x)).collect()
+    #[test]
+    fn classical_additive_decomposition() {
+        let df = frame(vec![("t", (0..16).map(i).collect()), ("v", ints(&QUARTERS))]);
+        let parts = df.seasonal_decompose("t", "v", 4, DecompositionModel::Additive, DecompositionMethod::Classical).unwrap();
+        assert_eq!(parts.labels, vec!["t", "observed", "trend", "seasonal", "residual"]);
 
-        }).collect()
+        let nan = f64::NAN;
+        all_close(&values(&parts, "trend"), &[nan, nan, 15.0, 16.75, 18.5, 20.5, 21.625, 22.125, 23.25, 24.375, 26.625, 29.875, 32.375, 36.375, nan, nan], 1e-12);
+        let seasonal = [-5.09375, 2.53125, -7.802083333333333, 10.364583333333334];
+        all_close(&values(&parts, "seasonal"), &seasonal.iter().cycle().take(16).cloned().collect::<Vec<f64>>(), 1e-12);
+        all_close(&values(&parts, "residual"), &[nan, nan, 0.8020833333333334, -2.1145833333333335, 2.59375, -1.03125, 0.17708333333333334,
+            2.5104166666666665, -3.15625, 0.09375, -0.8229166666666666, -0.23958333333333334, 0.71875, 1.09375, nan, nan], 1e-12);
+        assert!(parts.series("trend")[0].is_null());
     }
 
-    pub fn stddev(&self) -> Vec< Vec<DataTypes> > {
-        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+    #[test]
+    fn classical_multiplicative_decomposition() {
+        let df = frame(vec![("t", (0..16).map(i).collect()), ("v", ints(&QUARTERS))]);
+        let parts = df.seasonal_decompose("t", "v", 4, DecompositionModel::Multiplicative, DecompositionMethod::Classical).unwrap();
+        all_close(&values(&parts, "seasonal")[..4], &[0.7965711199567946, 1.100331336417197, 0.6227924122247066, 1.4803051314013018], 1e-12);
 
-            let op = match self.df.columns[ci].first().unwrap() {
-                DataTypes::FloatType(FloatType{value : _}) => { FloatType::stddev },
-                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::stddev },
-                DataTypes::StringType(StringType{value : _}) => { StringType::stddev },
-            };
+        let (trend, seasonal, residual) = (values(&parts, "trend"), values(&parts, "seasonal"), values(&parts, "residual"));
+        for t in 2..14 {
+            close(trend[t] * seasonal[t] * residual[t], QUARTERS[t] as f64, 1e-12);
+        }
+    }
 
-            let data : Vec<Vec<DataTypes>> = self.indices[i].iter().map( |(_, y)|
-                y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()
-            ).collect();
+    #[test]
+    fn loess_decomposition_recovers_trend_and_season() {
+        // a straight trend plus a fixed season is reproduced exactly by
+        // the local linear smoothers
+        let season = [3.0, -1.0, -2.0, 0.0];
+        let x : Vec<f64> = (0..24).map(|t| 2.0 * t as f64 + season[t % 4]).collect();
+        let df = frame(vec![("t", (0..24).map(i).collect()), ("v", floats(&x))]);
+        let parts = df.seasonal_decompose("t", "v", 4, DecompositionModel::Additive, DecompositionMethod::Loess).unwrap();
+
+        all_close(&values(&parts, "trend"), &(0..24).map(|t| 2.0 * t as f64).collect::<Vec<f64>>(), 1e-9);
+        all_close(&values(&parts, "seasonal"), &(0..24).map(|t| season[t % 4]).collect::<Vec<f64>>(), 1e-9);
+        assert!(values(&parts, "residual").iter().all(|r| r.abs() < 1e-9));
+
+        let growth : Vec<f64> = x.iter().map(|v| (v / 10.0).exp()).collect();
+        let df = frame(vec![("t", (0..24).map(i).collect()), ("v", floats(&growth))]);
+        let parts = df.seasonal_decompose("t", "v", 4, DecompositionModel::Multiplicative, DecompositionMethod::Loess).unwrap();
+        all_close(&values(&parts, "seasonal")[..4], &season.iter().map(|s| (s / 10.0).exp()).collect::<Vec<f64>>(), 1e-9);
+    }
 
-            data.iter().map(|x| op(x)).collect()
+    #[test]
+    fn decomposition_requires_increasing_dates() {
+        let days = strs(&["2000-01-01", "2000-01-02", "2000-01-03", "2000-01-04"]);
+        let df = frame(vec![("day", days), ("v", ints(&[1, 2, 3, 4]))]);
+        let parts = df.seasonal_decompose("day", "v", 2, DecompositionModel::Additive, DecompositionMethod::Classical).unwrap();
+        assert_eq!(text(&parts, "day"), vec!["2000-01-01", "2000-01-02", "2000-01-03", "2000-01-04"]);
+        assert_eq!(text(&parts, "observed"), vec!["1", "2", "3", "4"]);
+        assert_eq!(text(&parts, "trend"), vec!["null", "2", "3", "null"]);
+
+        let shuffled = frame(vec![("day", strs(&["2000-01-03", "2000-01-01", "2000-01-04", "2000-01-02"])), ("v", ints(&[3, 1, 4, 2]))]);
+        let repeated = frame(vec![("day", strs(&["2000-01-01", "2000-01-02", "2000-01-02", "2000-01-03"])), ("v", ints(&[1, 2, 3, 4]))]);
+        for df in [&shuffled, &repeated] {
+            assert!(matches!(df.seasonal_decompose("day", "v", 2, DecompositionModel::Additive, DecompositionMethod::Classical), Err(DataFrameError::NotSorted(_))));
+        }
 
-        }).collect()
+        // months differ in seconds but are one step of a yearly season
+        let months = strs(&["2000-01-01", "2000-02-01", "2000-03-01", "2000-04-01"]);
+        assert!(frame(vec![("month", months), ("v", ints(&[1, 2, 3, 4]))]).seasonal_decompose("month", "v", 2, DecompositionModel::Additive, DecompositionMethod::Classical).is_ok());
     }
 
-    pub fn pstddev(&self) -> Vec< Vec<DataTypes> > {
-        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+    #[test]
+    fn decomposition_rejects_bad_input() {
+        let df = frame(vec![
+            ("t", ints(&[0, 1, 2, 3, 4, 5])),
+            ("v", ints(&[1, 2, 3, 1, 2, 3])),
+            ("gap", vec![i(1), null(), i(3), i(1), i(2), i(3)]),
+            ("zero", ints(&[0, 2, 3, 1, 2, 3])),
+            ("s", strs(&["a", "b", "c", "a", "b", "c"])),
+        ]);
+        let decompose = |column, period, model| df.seasonal_decompose("t", column, period, model, DecompositionMethod::Classical);
+        assert!(decompose("v", 3, DecompositionModel::Additive).is_ok());
+        assert!(matches!(decompose("v", 1, DecompositionModel::Additive), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(decompose("v", 0, DecompositionModel::Additive), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(decompose("v", 4, DecompositionModel::Additive), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(decompose("gap", 3, DecompositionModel::Additive), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(decompose("zero", 3, DecompositionModel::Multiplicative), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(decompose("s", 3, DecompositionModel::Additive), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.seasonal_decompose("s", "v", 3, DecompositionModel::Additive, DecompositionMethod::Loess), Err(DataFrameError::InvalidArgument(_))));
+
+        let empty = frame(vec![("t", Vec::new()), ("v", Vec::new())]);
+        assert!(matches!(empty.seasonal_decompose("t", "v", 2, DecompositionModel::Additive, DecompositionMethod::Loess), Err(DataFrameError::InvalidArgument(_))));
+    }
 
-            let op = match self.df.columns[ci].first().unwrap() {
-                DataTypes::FloatType(FloatType{value : _}) => { FloatType::pstddev },
-                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::pstddev },
-                DataTypes::StringType(StringType{value : _}) => { StringType::pstddev },
-            };
 
-            let data : Vec<Vec<DataTypes>> = self.indices[i].iter().map( |(_, y)|
-                y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()
-            ).collect();
+    // interpolation, with reference values from pandas' interpolate
 
-            data.iter().map(|x| op(x)).collect()
+    #[test]
+    fn interpolate_methods_between_values() {
+        let df = frame(vec![("v", vec![i(1), i(3), null(), i(2), i(5)])]);
+        let fill = |method| values(&df.interpolate("v", method, None, None, LimitDirection::Forward).unwrap(), "v");
 
-        }).collect()
+        all_close(&fill(InterpolationMethod::Linear), &[1.0, 3.0, 2.5, 2.0, 5.0], 1e-12);
+        // the quadratic through (0, 1), (1, 3) and (3, 2)
+        all_close(&fill(InterpolationMethod::Polynomial(2)), &[1.0, 3.0, 3.3333333333333335, 2.0, 5.0], 1e-12);
+        // the natural cubic spline through every value
+        all_close(&fill(InterpolationMethod::CubicSpline), &[1.0, 3.0, 2.3125, 2.0, 5.0], 1e-12);
+
+        let filled = df.interpolate("v", InterpolationMethod::Linear, None, None, LimitDirection::Forward).unwrap();
+        assert!(matches!(filled.series("v")[0], DataTypes::FloatType(_)));
     }
 
-    pub fn variance(&self) -> Vec< Vec<DataTypes> > {
-        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+    #[test]
+    fn interpolate_nearest_keeps_the_value_type() {
+        let df = frame(vec![("v", vec![i(1), null(), null(), null(), i(5)])]);
+        let filled = df.interpolate("v", InterpolationMethod::Nearest, None, None, LimitDirection::Forward).unwrap();
+        assert_eq!(text(&filled, "v"), vec!["1", "1", "1", "5", "5"]);
+        assert!(filled.series("v").iter().all(|v| matches!(v, DataTypes::IntegerType(_))));
+    }
 
-            let op = match self.df.columns[ci].first().unwrap() {
-                DataTypes::FloatType(FloatType{value : _}) => { FloatType::variance },
-                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::variance },
-                DataTypes::StringType(StringType{value : _}) => { StringType::variance },
-            };
+    #[test]
+    fn interpolate_along_time_and_coordinates() {
+        let df = frame(vec![
+            ("day", strs(&["2000-01-01", "2000-01-02", "2000-01-05"])),
+            ("x", ints(&[0, 1, 4])),
+            ("v", vec![f(1.0), null(), f(5.0)]),
+        ]);
+        let time = df.interpolate("v", InterpolationMethod::Time, Some("day"), None, LimitDirection::Forward).unwrap();
+        all_close(&values(&time, "v"), &[1.0, 2.0, 5.0], 1e-12);
+        let nearest = df.interpolate("v", InterpolationMethod::Nearest, Some("x"), None, LimitDirection::Forward).unwrap();
+        all_close(&values(&nearest, "v"), &[1.0, 1.0, 5.0], 1e-12);
+        // linear always follows the row positions
+        let linear = df.interpolate("v", InterpolationMethod::Linear, Some("day"), None, LimitDirection::Forward).unwrap();
+        all_close(&values(&linear, "v"), &[1.0, 3.0, 5.0], 1e-12);
+
+        assert!(matches!(df.interpolate("v", InterpolationMethod::Time, None, None, LimitDirection::Forward), Err(DataFrameError::InvalidArgument(_))));
+        let unsorted = frame(vec![("x", ints(&[0, 2, 1])), ("v", vec![f(1.0), null(), f(5.0)])]);
+        assert!(matches!(unsorted.interpolate("v", InterpolationMethod::Time, Some("x"), None, LimitDirection::Forward), Err(DataFrameError::NotSorted(_))));
+        let gaps = frame(vec![("x", vec![i(0), null(), i(2)]), ("v", vec![f(1.0), null(), f(5.0)])]);
+        assert!(matches!(gaps.interpolate("v", InterpolationMethod::Time, Some("x"), None, LimitDirection::Forward), Err(DataFrameError::InvalidArgument(_))));
+    }
 
-            let data : Vec<Vec<DataTypes>> = self.indices[i].iter().map( |(_, y)|
-                y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()
-            ).collect();
+    #[test]
+    fn interpolate_limits_and_directions() {
+        let df = frame(vec![("v", vec![null(), f(1.0), null(), null(), null(), f(5.0), null(), null()])]);
+        let fill = |limit, direction| text(&df.interpolate("v", InterpolationMethod::Linear, None, limit, direction).unwrap(), "v");
+
+        assert_eq!(fill(None, LimitDirection::Forward), vec!["null", "1", "2", "3", "4", "5", "5", "5"]);
+        assert_eq!(fill(None, LimitDirection::Backward), vec!["1", "1", "2", "3", "4", "5", "null", "null"]);
+        assert_eq!(fill(None, LimitDirection::Both), vec!["1", "1", "2", "3", "4", "5", "5", "5"]);
+        assert_eq!(fill(Some(1), LimitDirection::Forward), vec!["null", "1", "2", "null", "null", "5", "5", "null"]);
+        assert_eq!(fill(Some(1), LimitDirection::Backward), vec!["1", "1", "null", "null", "4", "5", "null", "null"]);
+        // like pandas, `Both` counts a trailing run from its end as well
+        assert_eq!(fill(Some(1), LimitDirection::Both), vec!["1", "1", "2", "null", "4", "5", "5", "5"]);
+        assert_eq!(fill(Some(0), LimitDirection::Both), text(&df, "v"));
+    }
 
-            data.iter().map(|x| op(x)).collect()
+    #[test]
+    fn interpolate_empty_all_null_and_string_columns() {
+        let df = frame(vec![("n", vec![null(), null()]), ("s", strs(&["a", "b"]))]);
+        assert_eq!(text(&df.interpolate("n", InterpolationMethod::Linear, None, None, LimitDirection::Both).unwrap(), "n"), vec!["null", "null"]);
+        assert!(matches!(df.interpolate("s", InterpolationMethod::Linear, None, None, LimitDirection::Both), Err(DataFrameError::InvalidArgument(_))));
 
-        }).collect()
+        let empty = frame(vec![("v", Vec::new())]);
+        assert_eq!(empty.interpolate("v", InterpolationMethod::CubicSpline, None, None, LimitDirection::Both).unwrap().nrows(), 0);
     }
-    
-    pub fn simple_rolling_mean(&self, window : usize) -> Vec< Vec< Vec<DataTypes> > > {
-        self.column_indices.iter().enumerate().map( |(i, &ci)| {
 
-            let op = match self.df.columns[ci].first().unwrap() {
-                DataTypes::FloatType(FloatType{value : _}) => { FloatType::simple_rolling_mean },
-                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::simple_rolling_mean },
-                DataTypes::StringType(StringType{value : _}) => { StringType::simple_rolling_mean },
-            };
+    #[test]
+    fn group_interpolate_stays_within_groups() {
+        let df = frame(vec![
+            ("g", strs(&["a", "b", "a", "b", "a", "b"])),
+            ("v", vec![f(1.0), f(10.0), null(), null(), f(3.0), f(30.0)]),
+        ]);
+        let filled = df.group_by(vec!["g"]).interpolate("v", InterpolationMethod::Linear, None, None, LimitDirection::Forward).unwrap();
+        assert_eq!(filled.len(), 1);
+        all_close(&values(&filled[0], "v"), &[1.0, 10.0, 2.0, 20.0, 3.0, 30.0], 1e-12);
+
+        let trailing = frame(vec![("g", strs(&["a", "b", "a", "b"])), ("v", vec![f(1.0), null(), null(), f(4.0)])]);
+        let filled = trailing.group_by(vec!["g"]).interpolate("v", InterpolationMethod::Linear, None, None, LimitDirection::Forward).unwrap();
+        assert_eq!(text(&filled[0], "v"), vec!["1", "null", "1", "4"]);
+
+        assert!(matches!(df.group_by(vec!["g"]).interpolate("v", InterpolationMethod::Time, None, None, LimitDirection::Forward), Err(DataFrameError::InvalidArgument(_))));
+    }
 
-            let data : Vec<Vec<DataTypes>> = self.indices[i].iter().map( |(_, y)|
-                y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()
-            ).collect();
 
-            data.iter().map(|x| op(x, window)).collect()        
-        }).collect()
+    // missing values, matching pandas' fillna, dropna and isnull
+
+    fn gappy() -> DataFrame {
+        frame(vec![
+            ("a", vec![i(1), null(), null(), null(), i(5)]),
+            ("b", vec![null(), s("x"), null(), s("y"), null()]),
+            ("c", vec![f(0.5), f(f64::NAN), f(1.5), null(), null()]),
+        ])
     }
 
-    pub fn rolling_stddev(&self, window : usize) -> Vec< Vec< Vec<DataTypes> > > {
-        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+    #[test]
+    fn fillna_with_values_and_columns() {
+        let df = gappy();
+        let filled = df.fillna(FillNa::Value(i(0)), None).unwrap();
+        assert_eq!(text(&filled, "a"), vec!["1", "0", "0", "0", "5"]);
+        assert_eq!(text(&filled, "b"), vec!["0", "x", "0", "y", "0"]);
+        assert_eq!(text(&filled, "c"), vec!["0.5", "0", "1.5", "0", "0"]);
+
+        let mut map = HashMap::new();
+        map.insert(String::from("b"), s("z"));
+        let filled = df.fillna(FillNa::Columns(map), None).unwrap();
+        assert_eq!(text(&filled, "a"), text(&df, "a"));
+        assert_eq!(text(&filled, "b"), vec!["z", "x", "z", "y", "z"]);
+
+        let mut map = HashMap::new();
+        map.insert(String::from("missing"), s("z"));
+        assert!(matches!(df.fillna(FillNa::Columns(map), None), Err(DataFrameError::ColumnNotFound(_))));
+    }
 
-            let op = match self.df.columns[ci].first().unwrap() {
-                DataTypes::FloatType(FloatType{value : _}) => { FloatType::rolling_stddev },
-                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::rolling_stddev },
-                DataTypes::StringType(StringType{value : _}) => { StringType::rolling_stddev },
-            };
+    #[test]
+    fn fillna_forward_backward_and_limits() {
+        let df = gappy();
+        let forward = df.fillna(FillNa::Forward, None).unwrap();
+        assert_eq!(text(&forward, "a"), vec!["1", "1", "1", "1", "5"]);
+        assert_eq!(text(&forward, "b"), vec!["null", "x", "x", "y", "y"]);
+        assert_eq!(text(&forward, "c"), vec!["0.5", "0.5", "1.5", "1.5", "1.5"]);
+
+        let backward = df.fillna(FillNa::Backward, None).unwrap();
+        assert_eq!(text(&backward, "a"), vec!["1", "5", "5", "5", "5"]);
+        assert_eq!(text(&backward, "b"), vec!["x", "x", "y", "y", "null"]);
+
+        assert_eq!(text(&df.fillna(FillNa::Forward, Some(1)).unwrap(), "a"), vec!["1", "1", "null", "null", "5"]);
+        assert_eq!(text(&df.fillna(FillNa::Backward, Some(2)).unwrap(), "a"), vec!["1", "null", "5", "5", "5"]);
+        assert_eq!(text(&df.fillna(FillNa::Value(i(0)), Some(1)).unwrap(), "a"), vec!["1", "0", "null", "null", "5"]);
+        assert_eq!(text(&df.fillna(FillNa::Forward, Some(0)).unwrap(), "a"), text(&df, "a"));
+    }
 
-            let data : Vec<Vec<DataTypes>> = self.indices[i].iter().map( |(_, y)|
-                y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()
-            ).collect();
+    #[test]
+    fn dropna_any_all_subset_and_thresh() {
+        let df = gappy();
+        assert_eq!(df.dropna(DropHow::Any, None, None).unwrap().nrows(), 0);
+        assert_eq!(text(&df.dropna(DropHow::All, None, None).unwrap(), "a"), vec!["1", "null", "null", "null", "5"]);
+        assert_eq!(text(&df.dropna(DropHow::Any, Some(vec!["a", "c"]), None).unwrap(), "a"), vec!["1"]);
+        assert_eq!(text(&df.dropna(DropHow::All, Some(vec!["a", "c"]), None).unwrap(), "a"), vec!["1", "null", "5"]);
+        assert_eq!(text(&df.dropna(DropHow::Any, None, Some(2)).unwrap(), "a"), vec!["1"]);
+        assert_eq!(text(&df.dropna(DropHow::Any, None, Some(1)).unwrap(), "a"), vec!["1", "null", "null", "null", "5"]);
+        assert_eq!(df.dropna(DropHow::Any, Some(Vec::new()), None).unwrap().nrows(), 5);
+        assert!(matches!(df.dropna(DropHow::Any, Some(vec!["missing"]), None), Err(DataFrameError::ColumnNotFound(_))));
+
+        let nulls = frame(vec![("n", vec![null(), null()])]);
+        assert_eq!(nulls.dropna(DropHow::All, None, None).unwrap().nrows(), 0);
+        let empty = frame(vec![("n", Vec::new())]);
+        assert_eq!(empty.dropna(DropHow::Any, None, None).unwrap().nrows(), 0);
+    }
 
-            data.iter().map(|x| op(x, window)).collect()        
-        }).collect()
+    #[test]
+    fn null_masks_and_counts_treat_nan_as_null() {
+        let df = gappy();
+        assert_eq!(text(&df.isnull(), "c"), vec!["0", "1", "0", "1", "1"]);
+        assert_eq!(text(&df.notnull(), "c"), vec!["1", "0", "1", "0", "0"]);
+        assert_eq!(df.isnull().labels, df.labels);
+        assert_eq!(df.null_counts(), vec![(String::from("a"), 3), (String::from("b"), 3), (String::from("c"), 3)]);
+
+        let empty = frame(vec![("n", Vec::new())]);
+        assert_eq!(empty.null_counts(), vec![(String::from("n"), 0)]);
+        assert_eq!(empty.isnull().nrows(), 0);
     }
-    
-    pub fn rolling_variance(&self, window : usize) -> Vec< Vec< Vec<DataTypes> > > {
-        self.column_indices.iter().enumerate().map( |(i, &ci)| {
 
-            let op = match self.df.columns[ci].first().unwrap() {
-                DataTypes::FloatType(FloatType{value : _}) => { FloatType::rolling_variance },
-                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::rolling_variance },
-                DataTypes::StringType(StringType{value : _}) => { StringType::rolling_variance },
-            };
 
-            let data : Vec<Vec<DataTypes>> = self.indices[i].iter().map( |(_, y)|
-                y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()
-            ).collect();
+    // duplicates and distinct values, matching pandas' duplicated,
+    // drop_duplicates, unique and nunique
 
-            data.iter().map(|x| op(x, window)).collect()        
-        }).collect()
+    #[test]
+    fn duplicated_keeps_first_last_or_none() {
+        let df = frame(vec![("k", strs(&["a", "b", "a", "c", "a", "b"])), ("v", ints(&[1, 2, 1, 3, 4, 2]))]);
+        assert_eq!(df.duplicated(None, Keep::First).unwrap(), vec![false, false, true, false, false, true]);
+        assert_eq!(df.duplicated(None, Keep::Last).unwrap(), vec![true, true, false, false, false, false]);
+        assert_eq!(df.duplicated(None, Keep::None).unwrap(), vec![true, true, true, false, false, true]);
+        assert_eq!(df.duplicated(Some(vec!["k"]), Keep::First).unwrap(), vec![false, false, true, false, true, true]);
+        assert_eq!(df.duplicated(Some(vec!["k"]), Keep::Last).unwrap(), vec![true, true, true, false, false, false]);
+        assert!(matches!(df.duplicated(Some(vec!["missing"]), Keep::First), Err(DataFrameError::ColumnNotFound(_))));
+
+        // every row repeats the empty key
+        assert_eq!(df.duplicated(Some(Vec::new()), Keep::First).unwrap(), vec![false, true, true, true, true, true]);
+        let empty = frame(vec![("k", Vec::new())]);
+        assert!(empty.duplicated(None, Keep::None).unwrap().is_empty());
     }
 
-    pub fn diff(&self) -> Vec< Vec< Vec<DataTypes> > > {
-        self.column_indices.iter().enumerate().map( |(i, &ci)| {
+    #[test]
+    fn duplicated_compares_nulls_and_nans_by_kind() {
+        let df = frame(vec![("v", vec![null(), f(f64::NAN), null(), f(f64::NAN), i(1), f(1.0), f(0.0), f(-0.0)])]);
+        assert_eq!(df.duplicated(None, Keep::First).unwrap(), vec![false, false, true, true, false, false, false, true]);
+    }
 
-            let op = match self.df.columns[ci].first().unwrap() {
-                DataTypes::FloatType(FloatType{value : _}) => { FloatType::diff },
-                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::diff },
-                DataTypes::StringType(StringType{value : _}) => { StringType::diff },
-            };
+    #[test]
+    fn drop_duplicates_keeps_row_order() {
+        let df = frame(vec![("k", strs(&["a", "b", "a", "c", "a", "b"])), ("v", ints(&[1, 2, 1, 3, 4, 2]))]);
+        assert_eq!(text(&df.drop_duplicates(None, Keep::First).unwrap(), "v"), vec!["1", "2", "3", "4"]);
+        assert_eq!(text(&df.drop_duplicates(Some(vec!["k"]), Keep::Last).unwrap(), "v"), vec!["3", "4", "2"]);
+        assert_eq!(text(&df.drop_duplicates(Some(vec!["k"]), Keep::None).unwrap(), "v"), vec!["3"]);
+    }
 
-            let data : Vec< Vec<DataTypes> > = self.indices[i].iter().map( |(_, y)|
-                y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()
-            ).collect();
+    #[test]
+    fn unique_lists_one_null_for_nulls_and_nans() {
+        let df = frame(vec![
+            ("v", vec![i(2), null(), f(f64::NAN), i(1), i(2), f(1.0), null()]),
+            ("n", vec![null(); 7]),
+        ]);
+        assert_eq!(text(&df.unique("v").unwrap(), "v"), vec!["2", "null", "1", "1"]);
+        assert!(df.unique("v").unwrap().series("v")[1].is_null());
+        assert_eq!(text(&df.unique("n").unwrap(), "n"), vec!["null"]);
+        assert!(matches!(df.unique("missing"), Err(DataFrameError::ColumnNotFound(_))));
+
+        let empty = frame(vec![("v", Vec::new())]);
+        assert_eq!(empty.unique("v").unwrap().nrows(), 0);
+    }
 
-            data.iter().map(|x| op(x)).collect()
-        }).collect()
+    #[test]
+    fn nunique_skips_nulls() {
+        let df = frame(vec![
+            ("v", vec![i(2), null(), f(f64::NAN), i(1), i(2), f(1.0), null()]),
+            ("s", vec![s("a"), s("b"), s("a"), null(), s("c"), s("b"), s("a")]),
+            ("n", vec![null(); 7]),
+        ]);
+        assert_eq!(df.nunique(), vec![(String::from("v"), 3), (String::from("s"), 3), (String::from("n"), 0)]);
     }
-    
-    pub fn pct_change(&self) -> Vec< Vec< Vec<DataTypes> > > {
-        self.column_indices.iter().enumerate().map( |(i, &ci)| {
 
-            let op = match self.df.columns[ci].first().unwrap() {
-                DataTypes::FloatType(FloatType{value : _}) => { FloatType::pct_change },
-                DataTypes::IntegerType(IntegerType{value : _}) => { IntegerType::pct_change },
-                DataTypes::StringType(StringType{value : _}) => { StringType::pct_change },
-            };
 
-            let data : Vec<Vec<DataTypes>> = self.indices[i].iter().map( |(_, y)|
-                y.iter().map(|&b| self.df.columns[ci][b].clone()).collect()
-            ).collect();
+    // column selection, renaming and casting
 
-            data.iter().map(|x| op(x)).collect()
+    fn people() -> DataFrame {
+        frame(vec![("name", strs(&["ann", "bob"])), ("age", ints(&[31, 45])), ("score", floats(&[1.5, 2.0]))])
+    }
 
-        }).collect()
+    #[test]
+    fn drop_select_and_reorder_columns() {
+        let df = people();
+        assert_eq!(df.drop(vec!["age"]).unwrap().labels, vec!["name", "score"]);
+        assert_eq!(df.drop(Vec::new()).unwrap().labels, df.labels);
+        assert!(matches!(df.drop(vec!["age", "missing"]), Err(DataFrameError::ColumnNotFound(_))));
+
+        let selected = df.select(vec!["score", "name"]).unwrap();
+        assert_eq!(selected.labels, vec!["score", "name"]);
+        assert_eq!(text(&selected, "name"), vec!["ann", "bob"]);
+        assert!(matches!(df.select(vec!["name", "name"]), Err(DataFrameError::InvalidArgument(_))));
+        assert_eq!(df.select(Vec::new()).unwrap().ncols(), 0);
+
+        assert_eq!(df.reorder(vec!["age", "score", "name"]).unwrap().labels, vec!["age", "score", "name"]);
+        assert!(matches!(df.reorder(vec!["age", "name"]), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.reorder(vec!["age", "name", "name"]), Err(DataFrameError::InvalidArgument(_))));
     }
 
-    pub fn head(&self, num_rows : usize) {
-        let width = 15;
-        for lbl in self.labels.iter() { print!("{:^width$} ", lbl, width=width); }
-        print!("\n");
+    #[test]
+    fn rename_rejects_unknown_and_repeated_labels() {
+        let df = people();
+        assert_eq!(df.rename("age", "years").unwrap().labels, vec!["name", "years", "score"]);
+        assert!(matches!(df.rename("missing", "x"), Err(DataFrameError::ColumnNotFound(_))));
+        assert!(matches!(df.rename("age", "name"), Err(DataFrameError::InvalidArgument(_))));
+
+        // a swap is fine, the labels stay distinct
+        let mut mapping = HashMap::new();
+        mapping.insert("name", "age");
+        mapping.insert("age", "name");
+        let swapped = df.rename_columns(&mapping).unwrap();
+        assert_eq!(swapped.labels, vec!["age", "name", "score"]);
+        assert_eq!(text(&swapped, "age"), vec!["ann", "bob"]);
+    }
 
-        for row in 0..num_rows {
-            print!("{}", row);
-            for column in 0..self.columns.len() {
-                match self.columns[column][row] {
-                    DataTypes::FloatType(FloatType{value : _}) => {
-                        print!(" {:^width$}", self.columns[column][row].fvalue(), width=width);
-                    },
-                    DataTypes::IntegerType(IntegerType{value : _}) => {
-                        print!(" {:^width$}", self.columns[column][row].ivalue(), width=width);
-                    },
-                    DataTypes::StringType(StringType{value : _}) => { 
-                        print!(" {:^width$}", self.columns[column][row].svalue(), width=width);
-                    },
-                };
-            }
-            print!("\n");
+    #[test]
+    fn insert_column_checks_position_label_and_length() {
+        let mut df = people();
+        df.insert_column(1, &("id", ints(&[7, 8]))).unwrap();
+        assert_eq!(df.labels, vec!["name", "id", "age", "score"]);
+        df.insert_column(4, &("last", ints(&[0, 0]))).unwrap();
+        assert_eq!(df.labels.last().unwrap(), "last");
+
+        assert!(matches!(df.insert_column(6, &("far", ints(&[0, 0]))), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.insert_column(0, &("age", ints(&[0, 0]))), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.insert_column(0, &("short", ints(&[0]))), Err(DataFrameError::InvalidArgument(_))));
+        assert_eq!(df.ncols(), 5);
+
+        let mut empty = DataFrame::new();
+        empty.insert_column(0, &("a", ints(&[1, 2, 3]))).unwrap();
+        assert_eq!(empty.shape(), (3, 1));
+    }
+
+    #[test]
+    fn cast_strict_and_lenient() {
+        let df = frame(vec![
+            ("s", vec![s(" 12 "), s("3.0"), s("2.5"), s("x"), null()]),
+            ("f", vec![f(1.0), f(-2.0), f(2.5), f(f64::NAN), null()]),
+            ("i", vec![i(1), i(2), i(3), null(), i(5)]),
+        ]);
+
+        assert!(matches!(df.cast("s", ColumnType::Integer, CastMode::Strict), Err(DataFrameError::InvalidArgument(_))));
+        assert_eq!(text(&df.cast("s", ColumnType::Integer, CastMode::Lenient).unwrap(), "s"), vec!["12", "3", "null", "null", "null"]);
+        assert_eq!(text(&df.cast("s", ColumnType::Float, CastMode::Lenient).unwrap(), "s"), vec!["12", "3", "2.5", "null", "null"]);
+        assert_eq!(text(&df.cast("f", ColumnType::Integer, CastMode::Lenient).unwrap(), "f"), vec!["1", "-2", "null", "null", "null"]);
+        assert!(matches!(df.cast("f", ColumnType::Integer, CastMode::Strict), Err(DataFrameError::InvalidArgument(_))));
+
+        let floats = df.cast("i", ColumnType::Float, CastMode::Strict).unwrap();
+        assert!(matches!(floats.series("i")[0], DataTypes::FloatType(_)));
+        assert!(floats.series("i")[3].is_null());
+        assert_eq!(text(&df.cast("f", ColumnType::String, CastMode::Strict).unwrap(), "f"), vec!["1", "-2", "2.5", "null", "null"]);
+        assert_eq!(text(&floats, "s"), text(&df, "s"));
+
+        assert!(matches!(df.cast("i", ColumnType::Null, CastMode::Lenient), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.cast("missing", ColumnType::Float, CastMode::Lenient), Err(DataFrameError::ColumnNotFound(_))));
+    }
+
+    #[test]
+    fn dtypes_follow_the_first_non_null_value() {
+        let df = frame(vec![
+            ("f", vec![null(), f(1.0), i(2)]),
+            ("i", ints(&[1, 2, 3])),
+            ("s", vec![s("a"), i(1), null()]),
+            ("n", vec![null(); 3]),
+        ]);
+        assert_eq!(df.dtypes(), vec![
+            (String::from("f"), ColumnType::Float),
+            (String::from("i"), ColumnType::Integer),
+            (String::from("s"), ColumnType::String),
+            (String::from("n"), ColumnType::Null),
+        ]);
+        assert!(DataFrame::new().dtypes().is_empty());
+    }
+
+
+    // loading
+
+    #[test]
+    fn load_data_checks_lengths_and_labels() {
+        let mut df = people();
+        let ragged = vec![("a", ints(&[1, 2])), ("b", ints(&[1]))];
+        assert!(matches!(df.load_data(&ragged, false), Err(DataFrameError::InvalidArgument(_))));
+        let repeated = vec![("a", ints(&[1])), ("a", ints(&[2]))];
+        assert!(matches!(df.load_data(&repeated, false), Err(DataFrameError::InvalidArgument(_))));
+        assert_eq!(df.labels, vec!["name", "age", "score"]);
+
+        df.load_data(&repeated, true).unwrap();
+        assert_eq!(df.shape(), (1, 2));
+        df.load_data(&Vec::new(), false).unwrap();
+        assert_eq!(df.shape(), (0, 0));
+    }
+
+    #[test]
+    fn add_column_checks_length_and_label() {
+        let mut df = people();
+        assert!(matches!(df.add_column(&("age", ints(&[1, 2])), false), Err(DataFrameError::InvalidArgument(_))));
+        assert!(matches!(df.add_column(&("id", ints(&[1])), false), Err(DataFrameError::InvalidArgument(_))));
+        df.add_column(&("age", ints(&[1, 2])), true).unwrap();
+        df.add_column(&("id", ints(&[1, 2])), false).unwrap();
+        assert_eq!(df.shape(), (2, 5));
+        assert_eq!((df.nrows(), df.ncols()), df.shape());
+
+        let mut empty = DataFrame::new();
+        assert_eq!(empty.shape(), (0, 0));
+        empty.add_column(&("a", ints(&[1, 2, 3])), false).unwrap();
+        assert_eq!(empty.shape(), (3, 1));
+    }
+
+    // a csv file in the temporary directory, removed when dropped
+    //
+    struct TempCsv(std::path::PathBuf);
+
+    impl TempCsv {
+        fn new(name : &str, contents : &str) -> TempCsv {
+            let path = std::env::temp_dir().join(format!("framedata-{}-{}.csv", std::process::id(), name));
+            std::fs::write(&path, contents).unwrap();
+            TempCsv(path)
         }
     }
-    
-    pub fn print(&self) {
-        for (i, k) in self.column_indices.iter().enumerate().map(|(y, x)| (y, self.df.labels.get(*x).unwrap())) {
-            println!("{}", k);
-            for (val, rows) in self.indices.get(i).unwrap().iter() {
-                let k_str = val.svalue();
-                print!("{}\t", k_str);
-                for r in rows {
-                    print!("{} ", r);
-                }
-            }
-            println!("");
+
+    impl Drop for TempCsv {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn load_csv_parses_numbers_and_strings() {
+        let csv = TempCsv::new("good", "name,age,score\r\nann,31,1.5\r\nbob,45,x\r\n");
+        let mut df = DataFrame::new();
+        df.load_csv(&csv.0).unwrap();
+        assert_eq!(df.labels, vec!["name", "age", "score"]);
+        assert_eq!(df.shape(), (2, 3));
+        assert_eq!(text(&df, "name"), vec!["ann", "bob"]);
+        assert_eq!(values(&df, "age"), vec![31.0, 45.0]);
+        assert_eq!(text(&df, "score"), vec!["1.5", "x"]);
+
+        let header = TempCsv::new("header", "a,b\n");
+        df.load_csv(&header.0).unwrap();
+        assert_eq!(df.shape(), (0, 2));
+    }
+
+    #[test]
+    fn load_csv_rejects_bad_files_and_keeps_the_frame() {
+        let short = TempCsv::new("short", "a,b,c\n1,2,3\n4,5\n");
+        let long = TempCsv::new("long", "a,b\n1,2\n3,4,5\n");
+        let repeated = TempCsv::new("repeated", "a,b,a\n1,2,3\n");
+        let empty = TempCsv::new("empty", "");
+        let missing = std::env::temp_dir().join(format!("framedata-{}-missing.csv", std::process::id()));
+
+        let mut df = people();
+        for path in [&short.0, &long.0, &repeated.0, &empty.0, &missing].iter() {
+            assert!(matches!(df.load_csv(path), Err(DataFrameError::InvalidArgument(_))), "{} loaded", path.display());
+            assert_eq!(df.labels, vec!["name", "age", "score"]);
+            assert_eq!(df.shape(), (2, 3));
         }
     }
 }
@@ -0,0 +1,76 @@
+//  Copyright (c) 2020 Christopher Taylor
+//
+//  SPDX-License-Identifier: BSL-1.0
+//  Distributed under the Boost Software License, Version 1.0. (See accompanying
+//  file LICENSE_1_0.txt or copy at http://www.boost.org/LICENSE_1_0.txt)
+//
+// builders and float comparisons shared by the unit tests of every
+// module
+//
+use crate::{DataFrame, DataFrameError, DataTypes, FloatType, IntegerType, Series, StringType};
+
+pub(crate) fn f(value : f64) -> DataTypes {
+    DataTypes::FloatType(FloatType{value})
+}
+
+pub(crate) fn i(value : i64) -> DataTypes {
+    DataTypes::IntegerType(IntegerType{value})
+}
+
+pub(crate) fn s(value : &str) -> DataTypes {
+    DataTypes::StringType(StringType{value : value.to_string()})
+}
+
+pub(crate) fn null() -> DataTypes {
+    DataTypes::null()
+}
+
+pub(crate) fn floats(values : &[f64]) -> Series {
+    values.iter().map(|&v| f(v)).collect()
+}
+
+pub(crate) fn ints(values : &[i64]) -> Series {
+    values.iter().map(|&v| i(v)).collect()
+}
+
+pub(crate) fn strs(values : &[&str]) -> Series {
+    values.iter().map(|&v| s(v)).collect()
+}
+
+pub(crate) fn error<T>(result : Result<T, DataFrameError>) -> DataFrameError {
+    match result {
+        Ok(_) => panic!("expected an error"),
+        Err(e) => e,
+    }
+}
+
+pub(crate) fn frame(data : Vec<(&str, Series)>) -> DataFrame {
+    let mut df = DataFrame::new();
+    df.load_data(&data, false).unwrap();
+    df
+}
+
+// a column rendered with `svalue`, so mixed cells compare as text
+//
+pub(crate) fn text(df : &DataFrame, column_name : &str) -> Vec<String> {
+    df.series(column_name).iter().map(|v| v.svalue()).collect()
+}
+
+pub(crate) fn values(df : &DataFrame, column_name : &str) -> Vec<f64> {
+    df.series(column_name).iter().map(|v| v.fvalue()).collect()
+}
+
+pub(crate) fn close(actual : f64, expected : f64, tolerance : f64) {
+    assert!((actual - expected).abs() <= tolerance * expected.abs().max(1.0), "{} is not within {} of {}", actual, tolerance, expected);
+}
+
+pub(crate) fn all_close(actual : &[f64], expected : &[f64], tolerance : f64) {
+    assert_eq!(actual.len(), expected.len(), "{:?} and {:?} differ in length", actual, expected);
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        if e.is_nan() {
+            assert!(a.is_nan(), "{:?} is not {:?}", actual, expected);
+        } else {
+            close(*a, *e, tolerance);
+        }
+    }
+}